@@ -0,0 +1,338 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::broadcast;
+use tokio::sync::mpsc::{self, error::TrySendError};
+
+use crate::chat::events::{ChatEvent, DonationEvent, Event, EventKind};
+use crate::chat::filter::{Delivery, EventFilter, EventStats, EventStream};
+use crate::chat::options::SoopEventOptions;
+use crate::error::{Error, Result};
+
+struct FilteredSubscriber {
+    id: u64,
+    filter: EventFilter,
+    tx: mpsc::Sender<Delivery>,
+    // 보내지 못한 이벤트 수, 다음 이벤트 전에 Lagged로 알립니다.
+    skipped: u64,
+    // 무손실 구독이면 큐가 가득 찼을 때 기다릴 최대 시간
+    max_block: Option<Duration>,
+    // 무손실 구독에서 큐가 가득 차 아직 보내지 못한 이벤트
+    overflow: VecDeque<Event>,
+}
+
+impl FilteredSubscriber {
+    fn deliver(&mut self, event: Event, counters: &Counters) -> bool {
+        // 밀린 이벤트가 있으면 순서를 지키기 위해 뒤에 붙입니다.
+        if !self.overflow.is_empty() {
+            self.overflow.push_back(event);
+            return true;
+        }
+
+        if self.skipped > 0 {
+            match self.tx.try_send(Delivery::Lagged(self.skipped)) {
+                Ok(()) => self.skipped = 0,
+                Err(_) => return self.queue_or_drop(event, counters),
+            }
+        }
+
+        match self.tx.try_send(Delivery::Event(Box::new(event))) {
+            Ok(()) => true,
+            Err(TrySendError::Full(Delivery::Event(event))) => self.queue_or_drop(*event, counters),
+            Err(_) => false,
+        }
+    }
+
+    fn queue_or_drop(&mut self, event: Event, counters: &Counters) -> bool {
+        if self.max_block.is_some() {
+            self.overflow.push_back(event);
+            true
+        } else {
+            self.skipped += 1;
+            counters.dropped.fetch_add(1, Ordering::Relaxed);
+            false
+        }
+    }
+}
+
+#[derive(Default)]
+struct Counters {
+    sent: AtomicU64,
+    dropped: AtomicU64,
+}
+
+/// 한 연결에서 발생한 이벤트를 구독자에게 나눠 보냅니다.
+///
+/// - 전체 구독: `Raw`를 제외한 모든 이벤트
+/// - 진단 구독: `Error`, `ParseError`, `Unknown`
+/// - Raw 구독: 수신한 원본 패킷과, 연결이 끝났음을 알리는 `Disconnected`
+/// - 필터 구독: 필터를 통과한 이벤트만, 무손실 구독은 큐가 빌 때까지 연결 루프가 기다립니다.
+#[derive(Clone)]
+pub(crate) struct EventBus {
+    event_tx: broadcast::Sender<Event>,
+    diagnostic_tx: broadcast::Sender<Event>,
+    raw_tx: broadcast::Sender<Event>,
+    capacity: usize,
+    filtered: Arc<Mutex<Vec<FilteredSubscriber>>>,
+    next_id: Arc<AtomicU64>,
+    counters: Arc<Counters>,
+}
+
+impl EventBus {
+    pub fn new(options: &SoopEventOptions) -> Self {
+        // broadcast 채널은 크기가 0이면 패닉이 발생합니다.
+        let capacity = options.capacity.max(1);
+        let (event_tx, _) = broadcast::channel(capacity);
+        let (diagnostic_tx, _) = broadcast::channel(options.diagnostic_capacity.max(1));
+        let (raw_tx, _) = broadcast::channel(capacity);
+
+        Self {
+            event_tx,
+            diagnostic_tx,
+            raw_tx,
+            capacity,
+            filtered: Arc::new(Mutex::new(Vec::new())),
+            next_id: Arc::new(AtomicU64::new(0)),
+            counters: Arc::new(Counters::default()),
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Event> {
+        self.event_tx.subscribe()
+    }
+
+    pub fn subscribe_diagnostics(&self) -> broadcast::Receiver<Event> {
+        self.diagnostic_tx.subscribe()
+    }
+
+    pub fn subscribe_raw(&self) -> broadcast::Receiver<Event> {
+        self.raw_tx.subscribe()
+    }
+
+    pub fn subscribe_filtered<T>(
+        &self,
+        filter: EventFilter,
+        max_block: Option<Duration>,
+        map: fn(Event) -> Option<T>,
+    ) -> EventStream<T> {
+        let (tx, rx) = mpsc::channel(self.capacity);
+        self.filtered.lock().unwrap().push(FilteredSubscriber {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            filter,
+            tx,
+            skipped: 0,
+            max_block,
+            overflow: VecDeque::new(),
+        });
+        EventStream::new(rx, map)
+    }
+
+    pub fn subscribe_donations(&self) -> EventStream<DonationEvent> {
+        let filter = EventFilter::new().kind(EventKind::Donation);
+        self.subscribe_filtered(filter, None, |event| match event {
+            Event::Donation(e) => Some(e),
+            _ => None,
+        })
+    }
+
+    pub fn subscribe_chats(&self) -> EventStream<ChatEvent> {
+        let filter = EventFilter::new().kind(EventKind::Chat);
+        self.subscribe_filtered(filter, None, |event| match event {
+            Event::Chat(e) => Some(e),
+            _ => None,
+        })
+    }
+
+    pub fn stats(&self) -> EventStats {
+        EventStats {
+            sent: self.counters.sent.load(Ordering::Relaxed),
+            dropped: self.counters.dropped.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Raw 이벤트를 받을 구독자가 있는지 여부, 없으면 Raw 이벤트를 만들지 않습니다.
+    pub fn wants_raw(&self) -> bool {
+        self.raw_tx.receiver_count() > 0
+            || self
+                .filtered
+                .lock()
+                .unwrap()
+                .iter()
+                .any(|subscriber| subscriber.filter.accepts_kind(EventKind::Raw))
+    }
+
+    /// 이벤트를 구독자에게 보냅니다. 받은 구독자가 없으면 오류를 반환합니다.
+    pub fn send(&self, event: Event) -> Result<()> {
+        self.counters.sent.fetch_add(1, Ordering::Relaxed);
+        let mut delivered = self.send_filtered(&event);
+
+        // 진단 이벤트는 진단 채널에도 함께 방송합니다.
+        if event.is_diagnostic() {
+            delivered |= self.diagnostic_tx.send(event.clone()).is_ok();
+        }
+
+        // Raw 구독자도 연결이 끝났음을 알 수 있도록 Disconnected를 함께 보냅니다.
+        if matches!(event, Event::Disconnected(_)) {
+            delivered |= self.raw_tx.send(event.clone()).is_ok();
+        }
+
+        let tx = match event {
+            Event::Raw(_) => &self.raw_tx,
+            _ => &self.event_tx,
+        };
+        delivered |= tx.send(event).is_ok();
+
+        if delivered {
+            Ok(())
+        } else {
+            Err(Error::InternalChannel(
+                "이벤트를 받을 구독자가 없습니다.".to_string(),
+            ))
+        }
+    }
+
+    fn send_filtered(&self, event: &Event) -> bool {
+        let mut delivered = false;
+        let mut filtered = self.filtered.lock().unwrap();
+
+        // 스트림을 버린 구독자는 정리합니다.
+        filtered.retain(|subscriber| !subscriber.tx.is_closed());
+        for subscriber in filtered.iter_mut() {
+            if subscriber.filter.matches(event) {
+                delivered |= subscriber.deliver(event.clone(), &self.counters);
+            }
+        }
+        delivered
+    }
+
+    /// 무손실 구독에 밀린 이벤트를 보냅니다.
+    ///
+    /// 구독자의 큐에 자리가 날 때까지 구독마다 최대 `max_block`만큼 기다리며,
+    /// 그래도 자리가 나지 않으면 밀린 이벤트를 버리고 `Lagged`로 알립니다.
+    pub async fn flush(&self) {
+        let mut deadlines = HashMap::new();
+        loop {
+            // 잠금을 쥔 채로 기다리지 않도록, 보낼 구독자의 정보만 복사합니다.
+            let pending = {
+                let filtered = self.filtered.lock().unwrap();
+                filtered
+                    .iter()
+                    .find(|subscriber| !subscriber.overflow.is_empty())
+                    .map(|subscriber| {
+                        (
+                            subscriber.id,
+                            subscriber.tx.clone(),
+                            subscriber.max_block.unwrap_or_default(),
+                        )
+                    })
+            };
+            let Some((id, tx, max_block)) = pending else {
+                break;
+            };
+
+            let deadline = *deadlines
+                .entry(id)
+                .or_insert_with(|| tokio::time::Instant::now() + max_block);
+            let permit = tokio::time::timeout_at(deadline, tx.reserve()).await;
+
+            let mut filtered = self.filtered.lock().unwrap();
+            let Some(subscriber) = filtered.iter_mut().find(|subscriber| subscriber.id == id)
+            else {
+                continue;
+            };
+            match permit {
+                Ok(Ok(permit)) if subscriber.skipped > 0 => {
+                    permit.send(Delivery::Lagged(subscriber.skipped));
+                    subscriber.skipped = 0;
+                }
+                Ok(Ok(permit)) => {
+                    if let Some(event) = subscriber.overflow.pop_front() {
+                        permit.send(Delivery::Event(Box::new(event)));
+                    }
+                }
+                // 구독자가 스트림을 버렸습니다.
+                Ok(Err(_)) => subscriber.overflow.clear(),
+                // 기다려도 자리가 나지 않으면 밀린 이벤트를 버립니다.
+                Err(_) => {
+                    let dropped = subscriber.overflow.len() as u64;
+                    subscriber.overflow.clear();
+                    subscriber.skipped += dropped;
+                    self.counters.dropped.fetch_add(dropped, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+
+    /// 밀린 이벤트를 보내고, 필터 구독 스트림이 끝나도록 보내는 쪽을 닫습니다.
+    /// 연결이 완전히 종료된 뒤에 호출합니다.
+    pub async fn close(&self) {
+        self.flush().await;
+        self.filtered.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chat::events::{EventMeta, SlowEvent};
+    use chrono::Utc;
+
+    fn slow(duration: u32) -> Event {
+        Event::Slow(SlowEvent {
+            meta: EventMeta::new(Utc::now()),
+            duration,
+        })
+    }
+
+    fn bus(capacity: usize) -> EventBus {
+        EventBus::new(&SoopEventOptions {
+            capacity,
+            ..Default::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_lossy_subscriber_reports_lag() {
+        let bus = bus(2);
+        let mut stream = bus.subscribe_filtered(EventFilter::new(), None, Some);
+
+        for i in 0..5 {
+            bus.send(slow(i)).ok();
+        }
+        assert_eq!(bus.stats().dropped, 3);
+
+        // 큐에 있던 두 이벤트를 받은 뒤, 다음 이벤트 앞에서 놓친 수를 알립니다.
+        assert!(matches!(stream.recv().await, Some(Ok(Event::Slow(e))) if e.duration == 0));
+        assert!(matches!(stream.recv().await, Some(Ok(Event::Slow(e))) if e.duration == 1));
+        bus.send(slow(5)).ok();
+        assert!(matches!(stream.recv().await, Some(Err(lagged)) if lagged.skipped == 3));
+        assert!(matches!(stream.recv().await, Some(Ok(Event::Slow(e))) if e.duration == 5));
+    }
+
+    #[tokio::test]
+    async fn test_lossless_subscriber_waits_for_capacity() {
+        let bus = bus(1);
+        let mut stream =
+            bus.subscribe_filtered(EventFilter::new(), Some(Duration::from_secs(5)), Some);
+
+        let reader = tokio::spawn(async move {
+            let mut received = Vec::new();
+            while let Some(Ok(Event::Slow(e))) = stream.recv().await {
+                received.push(e.duration);
+            }
+            received
+        });
+
+        for i in 0..10 {
+            bus.send(slow(i)).ok();
+            bus.flush().await;
+        }
+        bus.close().await;
+
+        assert_eq!(reader.await.unwrap(), (0..10).collect::<Vec<_>>());
+        assert_eq!(bus.stats().dropped, 0);
+        assert_eq!(bus.stats().sent, 10);
+    }
+}
@@ -0,0 +1,292 @@
+use std::io::{self, Read, Write};
+
+use chrono::{DateTime, Utc};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+use crate::chat::events::Event;
+use crate::error::{Error, Result};
+
+/// 녹화 파일의 시작을 나타내는 바이트, 마지막 바이트는 형식 버전입니다.
+const MAGIC: &[u8; 8] = b"SOOPCAP\x01";
+
+/// 녹화 파일에 기록된 하나의 패킷
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedFrame {
+    pub received_time: DateTime<Utc>,
+    pub data: Vec<u8>,
+}
+
+/// 수신한 패킷을 녹화 파일 형식으로 기록합니다.
+///
+/// 파일은 헤더(시작 바이트, 스트리머 ID, 채팅방 번호) 뒤에
+/// `수신 시간(i64, 마이크로초) | 길이(u32) | 패킷` 레코드가 이어지는 형태이며,
+/// 모든 숫자는 리틀 엔디언으로 기록합니다.
+pub struct CaptureWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> CaptureWriter<W> {
+    /// 헤더를 기록하고 패킷을 기록할 준비를 합니다.
+    pub fn new(mut writer: W, streamer_id: &str, chat_no: &str) -> Result<Self> {
+        writer.write_all(MAGIC)?;
+        write_str(&mut writer, streamer_id)?;
+        write_str(&mut writer, chat_no)?;
+        Ok(Self { writer })
+    }
+
+    pub fn write_frame(&mut self, received_time: DateTime<Utc>, data: &[u8]) -> Result<()> {
+        let len = u32::try_from(data.len())
+            .map_err(|_| Error::InvalidCapture(format!("패킷이 너무 큽니다: {}", data.len())))?;
+
+        self.writer
+            .write_all(&received_time.timestamp_micros().to_le_bytes())?;
+        self.writer.write_all(&len.to_le_bytes())?;
+        self.writer.write_all(data)?;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// 버퍼를 비우고 내부 writer를 돌려받습니다.
+    pub fn into_inner(mut self) -> Result<W> {
+        self.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// 녹화 파일에서 패킷을 순서대로 읽습니다.
+pub struct CaptureReader<R: Read> {
+    reader: R,
+    streamer_id: String,
+    chat_no: String,
+}
+
+impl<R: Read> CaptureReader<R> {
+    /// 헤더를 읽고 형식을 확인합니다.
+    pub fn new(mut reader: R) -> Result<Self> {
+        let mut magic = [0u8; 8];
+        reader
+            .read_exact(&mut magic)
+            .map_err(|_| Error::InvalidCapture("헤더가 없습니다.".to_string()))?;
+        if &magic != MAGIC {
+            return Err(Error::InvalidCapture(
+                "녹화 파일 형식이 아니거나 지원하지 않는 버전입니다.".to_string(),
+            ));
+        }
+
+        let streamer_id = read_str(&mut reader)?;
+        let chat_no = read_str(&mut reader)?;
+        Ok(Self {
+            reader,
+            streamer_id,
+            chat_no,
+        })
+    }
+
+    /// 녹화한 채널의 스트리머 ID
+    pub fn streamer_id(&self) -> &str {
+        &self.streamer_id
+    }
+
+    /// 녹화를 시작할 때의 채팅방 번호
+    pub fn chat_no(&self) -> &str {
+        &self.chat_no
+    }
+
+    /// 다음 패킷을 읽습니다. 파일이 끝났다면 `None`을 반환합니다.
+    pub fn next_frame(&mut self) -> Result<Option<CapturedFrame>> {
+        let mut time = [0u8; 8];
+        // 레코드 경계에서 파일이 끝난 경우만 정상 종료로 봅니다.
+        match self.reader.read(&mut time[..1])? {
+            0 => return Ok(None),
+            _ => self.read_record(&mut time[1..])?,
+        }
+
+        let micros = i64::from_le_bytes(time);
+        let received_time = DateTime::from_timestamp_micros(micros)
+            .ok_or_else(|| Error::InvalidCapture(format!("잘못된 수신 시간: {micros}")))?;
+
+        let mut len = [0u8; 4];
+        self.read_record(&mut len)?;
+        let mut data = vec![0u8; u32::from_le_bytes(len) as usize];
+        self.read_record(&mut data)?;
+
+        Ok(Some(CapturedFrame {
+            received_time,
+            data,
+        }))
+    }
+
+    fn read_record(&mut self, buf: &mut [u8]) -> Result<()> {
+        self.reader.read_exact(buf).map_err(|e| match e.kind() {
+            io::ErrorKind::UnexpectedEof => {
+                Error::InvalidCapture("레코드가 중간에 끊겼습니다.".to_string())
+            }
+            _ => Error::Io(e),
+        })
+    }
+}
+
+impl<R: Read> Iterator for CaptureReader<R> {
+    type Item = Result<CapturedFrame>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_frame().transpose()
+    }
+}
+
+/// 연결의 `Event::Raw`를 녹화 파일로 기록합니다.
+///
+/// 헤더의 스트리머 ID와 채팅방 번호는 첫 패킷의 메타 정보에서 가져옵니다.
+/// `subscribe_raw`로 얻은 수신기를 넘겨주세요. 연결을 시작하기 전에 구독해야 합니다.
+/// `Disconnected` 이벤트를 받거나 연결이 사라지면 기록을 마치고, 기록한 패킷 수를 반환합니다.
+/// 수신기가 밀려 패킷을 놓치면 그때까지 기록한 내용을 남기고 `Error::CaptureLagged`를 반환합니다.
+pub fn spawn_recorder<W>(
+    mut events: broadcast::Receiver<Event>,
+    writer: W,
+) -> JoinHandle<Result<u64>>
+where
+    W: Write + Send + 'static,
+{
+    // 파일 쓰기가 런타임을 막지 않도록 별도 스레드에서 기록합니다.
+    tokio::task::spawn_blocking(move || {
+        let mut writer = Some(writer);
+        let mut capture = None;
+        let mut frames = 0;
+
+        let result = loop {
+            match events.blocking_recv() {
+                Ok(Event::Raw(raw)) => {
+                    if let Some(writer) = writer.take() {
+                        capture = Some(CaptureWriter::new(
+                            writer,
+                            &raw.meta.streamer_id,
+                            &raw.meta.chat_no,
+                        )?);
+                    }
+                    if let Some(capture) = capture.as_mut() {
+                        capture.write_frame(raw.meta.received_time, &raw.data)?;
+                        frames += 1;
+                    }
+                }
+                Ok(Event::Disconnected(_)) | Err(broadcast::error::RecvError::Closed) => {
+                    break Ok(frames);
+                }
+                // 빠진 패킷이 있는 녹화는 재생 결과가 달라지므로 오류로 알립니다.
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    break Err(Error::CaptureLagged { skipped });
+                }
+                Ok(_) => continue,
+            }
+        };
+
+        // 패킷을 하나도 받지 못했더라도 읽을 수 있는 파일을 남깁니다.
+        if let Some(writer) = writer {
+            capture = Some(CaptureWriter::new(writer, "", "")?);
+        }
+        if let Some(capture) = capture {
+            capture.into_inner()?;
+        }
+        result
+    })
+}
+
+fn write_str(writer: &mut impl Write, value: &str) -> Result<()> {
+    let len = u16::try_from(value.len())
+        .map_err(|_| Error::InvalidCapture(format!("문자열이 너무 깁니다: {}", value.len())))?;
+    writer.write_all(&len.to_le_bytes())?;
+    writer.write_all(value.as_bytes())?;
+    Ok(())
+}
+
+fn read_str(reader: &mut impl Read) -> Result<String> {
+    let mut len = [0u8; 2];
+    reader
+        .read_exact(&mut len)
+        .map_err(|_| Error::InvalidCapture("헤더가 중간에 끊겼습니다.".to_string()))?;
+    let mut value = vec![0u8; u16::from_le_bytes(len) as usize];
+    reader
+        .read_exact(&mut value)
+        .map_err(|_| Error::InvalidCapture("헤더가 중간에 끊겼습니다.".to_string()))?;
+    String::from_utf8(value)
+        .map_err(|_| Error::InvalidCapture("헤더가 UTF-8이 아닙니다.".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chat::bus::EventBus;
+    use crate::chat::events::{DisconnectedEvent, EventMeta, RawEvent};
+    use crate::chat::options::SoopEventOptions;
+
+    fn raw(data: &[u8]) -> Event {
+        Event::Raw(RawEvent {
+            meta: EventMeta::new(Utc::now()),
+            data: data.into(),
+        })
+    }
+
+    fn bus(capacity: usize) -> EventBus {
+        EventBus::new(&SoopEventOptions {
+            capacity,
+            ..Default::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_recorder_stops_on_disconnected() {
+        let bus = bus(16);
+        let recorder = spawn_recorder(bus.subscribe_raw(), Vec::new());
+
+        bus.send(raw(b"\x1b\tframe")).unwrap();
+        bus.send(Event::Disconnected(DisconnectedEvent {
+            meta: EventMeta::new(Utc::now()),
+        }))
+        .unwrap();
+
+        let frames = tokio::time::timeout(std::time::Duration::from_secs(5), recorder)
+            .await
+            .expect("녹화가 끝나지 않았습니다")
+            .unwrap();
+        assert_eq!(frames.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_recorder_reports_lag() {
+        let bus = bus(1);
+        let events = bus.subscribe_raw();
+        for _ in 0..3 {
+            bus.send(raw(b"\x1b\tframe")).unwrap();
+        }
+
+        let result = spawn_recorder(events, Vec::new()).await.unwrap();
+        assert!(matches!(result, Err(Error::CaptureLagged { skipped: 2 })));
+    }
+
+    #[test]
+    fn test_frames_round_trip() {
+        let time = DateTime::from_timestamp_micros(1_700_000_000_123_456).unwrap();
+        let mut writer = CaptureWriter::new(Vec::new(), "streamer", "42").unwrap();
+        writer.write_frame(time, b"\x1b\tframe").unwrap();
+        writer.write_frame(time, b"").unwrap();
+        let bytes = writer.into_inner().unwrap();
+
+        let mut reader = CaptureReader::new(bytes.as_slice()).unwrap();
+        assert_eq!(reader.streamer_id(), "streamer");
+        assert_eq!(reader.chat_no(), "42");
+        let frames: Vec<_> = reader.by_ref().collect::<Result<_>>().unwrap();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].received_time, time);
+        assert_eq!(frames[0].data, b"\x1b\tframe");
+
+        // 레코드 중간에서 끊긴 파일은 오류로 알립니다.
+        let truncated = &bytes[..bytes.len() - 3];
+        let mut reader = CaptureReader::new(truncated).unwrap();
+        assert!(reader.next_frame().unwrap().is_some());
+        assert!(matches!(reader.next_frame(), Err(Error::InvalidCapture(_))));
+    }
+}
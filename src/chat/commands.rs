@@ -1,4 +1,18 @@
-use crate::chat::constants::message_codes;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::oneshot;
+use tokio::time::Instant;
+
+use crate::{
+    Error, Result,
+    chat::{
+        constants::message_codes::{self, MessageCode},
+        parser::{raw::RawMessage, util::normalize_user_id},
+        types::{FreezeTarget, SuperUser},
+    },
+};
 
 // --- 채팅 명령어 타입 ---
 // format_message에서 사용됩니다.
@@ -8,6 +22,14 @@ pub enum MessageType {
     Ping,
     Connect,
     JOIN,
+    Chat,
+    // 관리 명령
+    Mute,
+    Kick,
+    KickCancel,
+    Freeze,
+    Slow,
+    Notice,
     Unknown,
 }
 
@@ -17,6 +39,13 @@ impl From<u32> for MessageType {
             message_codes::PING => Self::Ping,
             message_codes::CONNECT => Self::Connect,
             message_codes::JOIN => Self::JOIN,
+            message_codes::CHAT => Self::Chat,
+            message_codes::MUTE => Self::Mute,
+            message_codes::KICK => Self::Kick,
+            message_codes::KICK_CANCEL => Self::KickCancel,
+            message_codes::FREEZE => Self::Freeze,
+            message_codes::SLOW => Self::Slow,
+            message_codes::NOTIFICATION => Self::Notice,
             // 알 수 없는 명령어는 Unknown으로 처리합니다.
             _ => Self::Unknown,
         }
@@ -29,6 +58,13 @@ impl MessageType {
             Self::Ping => message_codes::PING,
             Self::Connect => message_codes::CONNECT,
             Self::JOIN => message_codes::JOIN,
+            Self::Chat => message_codes::CHAT,
+            Self::Mute => message_codes::MUTE,
+            Self::Kick => message_codes::KICK,
+            Self::KickCancel => message_codes::KICK_CANCEL,
+            Self::Freeze => message_codes::FREEZE,
+            Self::Slow => message_codes::SLOW,
+            Self::Notice => message_codes::NOTIFICATION,
             Self::Unknown => 0, // 알 수 없는 명령어는 0으로 처리
         }
     }
@@ -39,6 +75,194 @@ impl MessageType {
 pub enum Command {
     /// 채팅 메시지를 전송하라는 명령.
     SendChat(String),
+
+    // --- 관리 명령 (매니저 / BJ 계정 전용) ---
+    /// 사용자의 채팅을 일정 시간(초) 동안 금지하라는 명령.
+    Mute {
+        user_id: String,
+        seconds: u32,
+        /// 명령을 내리는 권한
+        superuser_type: SuperUser,
+    },
+    /// 사용자를 강제 퇴장시키라는 명령.
+    Kick { user_id: String },
+    /// 강제 퇴장을 취소하라는 명령.
+    KickCancel { user_id: String },
+    /// 채팅방을 얼리거나 녹이라는 명령.
+    Freeze {
+        freezed: bool,
+        /// 채팅을 허용할 대상
+        targets: Vec<FreezeTarget>,
+        limit_balloons: u32,
+        limit_subscription_month: u32,
+    },
+    /// 저속 모드를 설정하라는 명령, 0이면 해제합니다.
+    Slow { duration: u32 },
+    /// 공지사항을 등록하라는 명령.
+    Notice { message: String, show: bool },
     /// 모든 연결을 종료하고 태스크를 중단하라는 명령.
     Shutdown,
 }
+
+impl Command {
+    /// 명령이 처리되었을 때 서버가 보내는 응답 패킷의 조건입니다.
+    /// `user_id`는 로그인한 계정으로, 자신이 보낸 채팅을 찾는 데 사용합니다.
+    pub(crate) fn ack(&self, user_id: &str) -> Option<Ack> {
+        let ack = match self {
+            Command::SendChat(_) => Ack::field(message_codes::CHAT, 1, user_id),
+            Command::Mute { user_id, .. } => Ack::field(message_codes::MUTE, 0, user_id),
+            Command::Kick { user_id } => Ack::field(message_codes::KICK, 0, user_id),
+            Command::KickCancel { user_id } => Ack::field(message_codes::KICK_CANCEL, 1, user_id),
+            Command::Freeze { .. } => Ack::code(message_codes::FREEZE),
+            Command::Slow { .. } => Ack::code(message_codes::SLOW),
+            Command::Notice { .. } => Ack::code(message_codes::NOTIFICATION),
+            Command::Shutdown => return None,
+        };
+        Some(ack)
+    }
+}
+
+/// 명령이 처리되었음을 알리는 응답 패킷의 조건
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Ack {
+    code: MessageCode,
+    // (필드 위치, 사용자 ID), 같은 코드의 다른 사용자 패킷과 구분합니다.
+    user_field: Option<(usize, String)>,
+}
+
+impl Ack {
+    fn code(code: MessageCode) -> Self {
+        Self {
+            code,
+            user_field: None,
+        }
+    }
+
+    fn field(code: MessageCode, index: usize, user_id: &str) -> Self {
+        Self {
+            code,
+            user_field: Some((index, normalize_user_id(user_id))),
+        }
+    }
+
+    /// 명령이 성공했다는 응답인지 확인합니다.
+    fn is_accepted_by(&self, message: &RawMessage) -> bool {
+        message.code == self.code
+            && self.user_field.as_ref().is_none_or(|(index, user_id)| {
+                message
+                    .body
+                    .get(*index)
+                    .is_some_and(|field| normalize_user_id(field) == *user_id)
+            })
+    }
+}
+
+/// 서버의 응답을 기다리는 명령
+#[derive(Debug)]
+struct PendingCommand {
+    ack: Ack,
+    responder: CommandResponder,
+    deadline: Instant,
+}
+
+/// 전송한 뒤 서버의 응답을 기다리는 명령 목록입니다.
+///
+/// 응답 패킷을 받으면 같은 코드의 가장 오래된 명령부터 결과를 알립니다.
+/// 헤더의 응답 코드가 0이 아니면 서버가 명령을 거부한 것으로 봅니다.
+/// 세션이 끝나 목록이 버려지면, 기다리던 명령은 `ConnectionClosed`로 끝납니다.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PendingCommands {
+    commands: Arc<Mutex<VecDeque<PendingCommand>>>,
+}
+
+impl PendingCommands {
+    /// 응답을 기다릴 명령을 추가합니다.
+    pub fn push(&self, ack: Ack, responder: CommandResponder, timeout: Duration) {
+        self.commands.lock().unwrap().push_back(PendingCommand {
+            ack,
+            responder,
+            deadline: Instant::now() + timeout,
+        });
+    }
+
+    /// 수신한 메시지가 기다리던 명령의 응답이면 결과를 알립니다.
+    /// 거부 응답이었다면 true를 반환하며, 이 메시지는 이벤트로 만들지 않습니다.
+    ///
+    /// 거부 응답은 메시지 코드로만 명령을 찾으므로, 같은 종류의 명령이 여러 개 대기 중이면
+    /// 가장 먼저 보낸 명령이 거부된 것으로 처리됩니다.
+    pub fn resolve(&self, message: &RawMessage) -> bool {
+        let mut commands = self.commands.lock().unwrap();
+        let rejected = message.ret_code != 0;
+
+        // 거부 응답에는 대상 사용자가 없을 수 있으므로 코드만 비교합니다.
+        let position = commands.iter().position(|pending| {
+            if rejected {
+                pending.ack.code == message.code
+            } else {
+                pending.ack.is_accepted_by(message)
+            }
+        });
+        let Some(pending) = position.and_then(|position| commands.remove(position)) else {
+            return false;
+        };
+
+        let result = if rejected {
+            Err(Error::CommandRejected {
+                code: message.ret_code,
+                reason: message
+                    .body
+                    .iter()
+                    .find(|field| !field.is_empty())
+                    .cloned()
+                    .unwrap_or_default(),
+            })
+        } else {
+            Ok(())
+        };
+        respond(Some(pending.responder), result);
+        rejected
+    }
+
+    /// 가장 먼저 응답 시간이 끝나는 명령의 기한
+    pub fn next_deadline(&self) -> Option<Instant> {
+        let commands = self.commands.lock().unwrap();
+        commands.iter().map(|pending| pending.deadline).min()
+    }
+
+    /// 기한이 지난 명령을 `CommandTimeout`으로 끝냅니다.
+    pub fn expire(&self, now: Instant) {
+        let mut commands = self.commands.lock().unwrap();
+        let (expired, waiting): (VecDeque<_>, VecDeque<_>) = commands
+            .drain(..)
+            .partition(|pending| pending.deadline <= now);
+        *commands = waiting;
+
+        for pending in expired {
+            respond(Some(pending.responder), Err(Error::CommandTimeout));
+        }
+    }
+}
+
+/// 명령 실행 결과를 돌려받는 응답 채널.
+pub type CommandResponder = oneshot::Sender<Result<()>>;
+
+/// 백그라운드 연결 루프로 전달되는 명령과, 실행 결과를 알릴 응답 채널.
+#[derive(Debug)]
+pub struct CommandRequest {
+    pub(crate) command: Command,
+    pub(crate) responder: Option<CommandResponder>,
+}
+
+impl CommandRequest {
+    pub fn new(command: Command, responder: Option<CommandResponder>) -> Self {
+        Self { command, responder }
+    }
+}
+
+/// 응답 채널이 있는 경우에만 명령 실행 결과를 전달합니다.
+pub(crate) fn respond(responder: Option<CommandResponder>, result: Result<()>) {
+    if let Some(responder) = responder {
+        // 요청한 쪽이 더 이상 기다리지 않으면 결과를 버립니다.
+        let _ = responder.send(result);
+    }
+}
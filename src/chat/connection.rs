@@ -1,23 +1,34 @@
-use super::commands::Command;
-use super::events::Event;
-use super::options::SoopChatOptions;
+use super::commands::{Command, CommandRequest, CommandResponder, PendingCommands, respond};
+use super::events::{
+    BJStateChangeEvent, ChatEvent, ConnectedEvent, DisconnectedEvent, DonationEvent, ErrorEvent,
+    Event, EventMeta, EventStamp, ReconnectingEvent, StreamOfflineEvent, StreamOnlineEvent,
+};
+use super::options::{SoopChatOptions, SoopLoginOptions, SoopWatchOptions};
 use crate::SoopHttpClient;
+use crate::chat::bus::EventBus;
 use crate::chat::commands::MessageType;
+use crate::chat::constants::message_codes::MessageCode;
+use crate::chat::decoder::{DecoderRegistry, PacketDecoder};
+use crate::chat::filter::{EventFilter, EventStats, EventStream};
 use crate::chat::formatter::ChatFormatter;
 use crate::chat::message::MessageHandler;
-use crate::chat::verification::NoVerification;
+use crate::chat::parser::raw::{FrameDecoder, RawMessage};
+use crate::chat::reconnect::Backoff;
+use crate::chat::types::{BroadcastChange, BroadcastInfo};
+use crate::chat::verification::build_client_config;
 use crate::error::{Error, Result};
 use crate::models::LiveDetail;
+use chrono::Utc;
 use futures_util::lock::Mutex;
 use futures_util::stream::SplitStream;
 use futures_util::{SinkExt, StreamExt, stream::SplitSink};
 use reqwest::header::HeaderValue;
 use rustls::ClientConfig;
-use rustls::crypto::CryptoProvider;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::net::TcpStream;
-use tokio::sync::{broadcast, mpsc};
+use tokio::sync::{Semaphore, broadcast, mpsc, oneshot};
+use tokio::time::{Instant, Interval};
 use tokio_tungstenite::connect_async_tls_with_config;
 use tokio_tungstenite::tungstenite::client::IntoClientRequest;
 use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, tungstenite::protocol::Message};
@@ -26,61 +37,151 @@ use url::Url;
 /// SOOP 채팅 서버와의 실시간 연결을 관리하고,
 /// 자동 재연결을 포함한 모든 생명주기를 책임지는 핵심 구조체입니다.
 pub struct SoopChatConnection {
-    client: Arc<SoopHttpClient>,       // HTTP 클라이언트 (SOOP API 호출용)
-    command_tx: mpsc::Sender<Command>, // 명령을 보내는 채널
-    command_rx: Mutex<Option<mpsc::Receiver<Command>>>, // 명령을 받는 채널 (Mutex로 감싸서 안전하게 공유)
-    event_tx: broadcast::Sender<Event>,                 // 이벤트를 방송하는 채널
-    options: SoopChatOptions,                           // 채팅 옵션 (스트리머 ID 등)
+    client: Arc<SoopHttpClient>, // HTTP 클라이언트 (SOOP API 호출용)
+    command_tx: mpsc::Sender<CommandRequest>, // 명령을 보내는 채널
+    command_rx: Mutex<Option<mpsc::Receiver<CommandRequest>>>, // 명령을 받는 채널 (Mutex로 감싸서 안전하게 공유)
+    bus: EventBus,                         // 이벤트를 구독자에게 나눠 보내는 채널 묶음
+    options: SoopChatOptions,              // 채팅 옵션 (스트리머 ID 등)
+    tls_config: Arc<ClientConfig>,         // WebSocket 연결용 TLS 설정
+    decoders: DecoderRegistry,             // 사용자 정의 패킷 해석기
+    session_slots: Option<Arc<Semaphore>>, // 동시 연결 수 제한 (허브에서 공유)
 }
 
 // --- 내부 상태 관리용 구조체 ---
 struct ConnectionLoopState {
-    command_rx: mpsc::Receiver<Command>,
-    command_tx: mpsc::Sender<Command>,
-    event_tx: broadcast::Sender<Event>,
+    client: Arc<SoopHttpClient>,
+    command_rx: mpsc::Receiver<CommandRequest>,
+    command_tx: mpsc::Sender<CommandRequest>,
+    bus: EventBus,
+    streamer_id: String,
     connection_url: String,
-    live_detail: LiveDetail,
+    connection_url_override: Option<String>,
+    // 방송 중이 아니면 None (watch 모드에서만)
+    live_detail: Option<LiveDetail>,
     password: String,
+    login: Option<SoopLoginOptions>,
+    auth_ticket: Option<String>,
+    // 명령에 대한 서버 응답을 기다리는 시간
+    command_timeout: Duration,
+    backoff: Backoff,
+    // 방송 대기(watch) 모드 설정
+    watch: Option<SoopWatchOptions>,
+    tls_config: Arc<ClientConfig>,
+    decoders: DecoderRegistry,
+    // 연결 세션마다 얻어야 하는 슬롯, None이면 제한 없음
+    session_slots: Option<Arc<Semaphore>>,
+    // 이벤트에 붙일 채널 정보와 순번
+    stamp: EventStamp,
+    // 한 번이라도 연결에 성공했는지 여부 (Connected / Reconnected 구분용)
+    has_connected: bool,
+}
+
+impl ConnectionLoopState {
+    /// 채널 정보와 순번을 붙여 이벤트를 방송합니다. 구독자가 없어도 연결은 유지합니다.
+    fn emit(&self, event: Event) {
+        self.bus.send(self.stamp.stamp(event)).ok();
+    }
+
+    /// 연결 루프에서 발생한 오류를 진단 이벤트(`Event::Error`)로 알립니다.
+    fn report_error(&self, context: &str, e: &Error) {
+        self.emit(Event::Error(ErrorEvent {
+            meta: EventMeta::new(Utc::now()),
+            code: None,
+            reason: format!("{}: {}", context, e),
+            raw: Arc::from([]),
+        }));
+    }
+
+    /// 현재 방송 정보로 StreamOnline 이벤트를 방송합니다.
+    fn emit_stream_online(&self) {
+        if let Some(live_detail) = &self.live_detail {
+            self.emit(Event::StreamOnline(StreamOnlineEvent {
+                meta: EventMeta::new(Utc::now()),
+                title: live_detail.title.clone(),
+                streamer_nick: live_detail.streamer_nick.clone(),
+                categories: live_detail.categories.clone(),
+            }));
+        }
+    }
+
+    /// 방송이 종료된 상태로 전환하고 StreamOffline 이벤트를 방송합니다.
+    fn set_offline(&mut self) {
+        self.live_detail = None;
+        self.emit(Event::StreamOffline(StreamOfflineEvent {
+            meta: EventMeta::new(Utc::now()),
+        }));
+    }
 }
 
 impl SoopChatConnection {
     /// 새로운 SOOP 채팅 연결을 시작합니다.
     pub fn new(soop_http_client: Arc<SoopHttpClient>, options: SoopChatOptions) -> Result<Self> {
-        // * TLS 설정이 없는 경우에만 설정
-        if CryptoProvider::get_default().is_none() {
-            rustls::crypto::aws_lc_rs::default_provider()
-                .install_default()
-                .expect("Failed to install default crypto provider");
-        }
+        // TLS 설정은 연결마다 다시 만들지 않도록 미리 구성해 둡니다.
+        let tls_config = Arc::new(build_client_config(&options.tls)?);
         // 1. 통신 채널 생성
         // command 채널: 여러 곳에서 명령을 보낼 수 있지만, 받는 곳은 하나(mpsc)
         let (command_tx, command_rx) = mpsc::channel(32);
         // event 채널: 보내는 곳은 하나지만, 여러 곳에서 구독하여 들을 수 있음(broadcast)
-        let (event_tx, _) = broadcast::channel(1024);
+        // 진단 이벤트, Raw 이벤트, 필터 구독은 따로 구독할 수 있음
+        let bus = EventBus::new(&options.events);
         // 2. 사용자가 제어할 수 있는 핸들만 반환
         Ok(Self {
             command_tx,
             command_rx: Mutex::new(Some(command_rx)),
-            event_tx,
+            bus,
             client: soop_http_client,
+            tls_config,
+            decoders: DecoderRegistry::default(),
+            session_slots: None,
             options,
         })
     }
 
+    /// WebSocket 세션마다 `slots`에서 슬롯을 얻은 뒤 접속하도록 합니다.
+    /// 슬롯은 세션이 끝나면 반납되므로, 재연결 대기나 방송 대기 중에는 차지하지 않습니다.
+    pub(crate) fn with_session_slots(mut self, slots: Arc<Semaphore>) -> Self {
+        self.session_slots = Some(slots);
+        self
+    }
+
+    /// 명령을 큐에 넣고 바로 반환합니다.
+    /// 명령의 실행 결과가 필요하다면 `execute`를 사용합니다.
     pub fn command(&self, command: Command) -> Result<()> {
         // 명령을 보내는 채널에 전송합니다.
         self.command_tx
-            .try_send(command)
+            .try_send(CommandRequest::new(command, None))
             .map_err(|e| Error::InternalChannel(e.to_string()))
     }
 
-    fn make_connection_url(&self, live_detail: &LiveDetail) -> String {
-        format!(
-            "wss://{}:{}/Websocket/{}",
-            live_detail.ch_domain.to_lowercase(),
-            live_detail.ch_pt + 1,
-            self.options.streamer_id
-        )
+    /// 명령을 전송하고, 서버가 명령을 처리했다는 응답을 보낼 때까지 기다립니다.
+    ///
+    /// 서버가 명령을 거부하면 `Error::CommandRejected`를,
+    /// `SoopChatOptions::command_timeout` 안에 응답이 없으면 `Error::CommandTimeout`을 반환합니다.
+    /// `start` 이전에 호출하면 연결이 시작되어 명령이 처리될 때까지 대기합니다.
+    ///
+    /// 거부 응답에는 대상이 담기지 않아 메시지 코드로만 명령을 구분합니다.
+    /// 같은 종류의 명령을 동시에 여러 개 실행하면 거부가 가장 먼저 보낸 명령에 전달될 수 있으니,
+    /// 결과를 정확히 구분해야 한다면 이전 명령의 응답을 받은 뒤 다음 명령을 실행하세요.
+    pub async fn execute(&self, command: Command) -> Result<()> {
+        let (responder, response) = oneshot::channel();
+
+        self.command_tx
+            .send(CommandRequest::new(command, Some(responder)))
+            .await
+            .map_err(|_| Error::ConnectionClosed)?;
+
+        // 응답 채널이 닫혔다면 연결 루프가 종료된 것입니다.
+        response.await.map_err(|_| Error::ConnectionClosed)?
+    }
+
+    /// 채팅 메시지를 전송하고, 서버가 채팅을 받아들였는지 기다립니다.
+    /// 로그인 정보(`SoopChatOptions::login`)가 설정된 경우에만 사용할 수 있습니다.
+    pub async fn send_chat(&self, message: impl Into<String>) -> Result<()> {
+        if self.options.login.is_none() {
+            return Err(Error::NotAuthenticated);
+        }
+
+        self.execute(Command::SendChat(message.into())).await
     }
 
     pub async fn start(&self) -> Result<()> {
@@ -90,31 +191,62 @@ impl SoopChatConnection {
             .get_live_detail_state(&self.options.streamer_id)
             .await?;
 
-        // 오프라인이면 종료합니다.
-        if !is_live {
-            return Err(Error::StreamOffline);
-        } else if optional_live_detail.is_none() {
-            return Err(Error::InternalChannel(
-                "생방송 정보가 잘못되었습니다.".to_string(),
-            ));
-        }
+        // 오프라인이면 종료합니다. 방송 대기(watch) 모드에서는 방송이 시작될 때까지 기다립니다.
+        let live_detail = match (is_live, optional_live_detail) {
+            (true, Some(live_detail)) => Some(live_detail),
+            (true, None) => {
+                return Err(Error::InternalChannel(
+                    "생방송 정보가 잘못되었습니다.".to_string(),
+                ));
+            }
+            (false, _) if self.options.watch.is_some() => None,
+            (false, _) => return Err(Error::StreamOffline),
+        };
 
-        let live_detail = optional_live_detail.unwrap();
+        // 로그인 정보가 있으면 채팅 인증 티켓을 발급받습니다.
+        let auth_ticket = authenticate(&self.client, self.options.login.as_ref()).await?;
 
         // websocket url 생성
-        let connection_url = self.make_connection_url(&live_detail);
+        let connection_url = live_detail
+            .as_ref()
+            .map(|live_detail| {
+                make_connection_url(
+                    live_detail,
+                    &self.options.streamer_id,
+                    self.options.connection_url.as_deref(),
+                )
+            })
+            .unwrap_or_default();
+        let chat_no = live_detail
+            .as_ref()
+            .map(|live_detail| live_detail.ch_no.as_str())
+            .unwrap_or_default();
 
         // 소유권 을 안전하게 가져오기 위해, command_rx를 잠급니다.
         let mut rx_guard = self.command_rx.lock().await;
         // 소유권을 이전합니다.
         if let Some(command_rx) = rx_guard.take() {
+            let stamp = EventStamp::new(&self.options.streamer_id, chat_no);
             let loop_state = ConnectionLoopState {
+                client: Arc::clone(&self.client),
                 command_tx: self.command_tx.clone(),
                 command_rx,
-                event_tx: self.event_tx.clone(),
+                bus: self.bus.clone(),
+                streamer_id: self.options.streamer_id.clone(),
                 connection_url,
+                connection_url_override: self.options.connection_url.clone(),
                 live_detail,
                 password: self.options.password.clone(),
+                login: self.options.login.clone(),
+                auth_ticket,
+                command_timeout: self.options.command_timeout,
+                backoff: Backoff::new(self.options.reconnect.clone()),
+                watch: self.options.watch.clone(),
+                tls_config: Arc::clone(&self.tls_config),
+                decoders: self.decoders.clone(),
+                session_slots: self.session_slots.clone(),
+                stamp,
+                has_connected: false,
             };
             // 백그라운드 스레드 실행
             tokio::spawn(run_connection_loop(loop_state));
@@ -125,67 +257,358 @@ impl SoopChatConnection {
         }
     }
 
+    /// 메시지 코드에 사용자 정의 해석기를 등록합니다.
+    /// 같은 코드의 기존 해석기(기본 해석기 포함)를 대체하며, 연결 중에도 등록할 수 있습니다.
+    /// `CONNECT`, `BJ_STATE_CHANGE`는 연결 유지에 필요하므로 등록할 수 없습니다.
+    pub fn register_decoder(&self, decoder: impl PacketDecoder + 'static) -> Result<()> {
+        self.decoders.register(Arc::new(decoder))
+    }
+
+    /// 등록한 해석기를 제거하고 기본 해석기로 되돌립니다. 제거했다면 true를 반환합니다.
+    pub fn unregister_decoder(&self, code: MessageCode) -> bool {
+        self.decoders.unregister(code)
+    }
+
     /// 라이브러리가 방송하는 이벤트를 수신할 "수신기"를 얻습니다.
+    /// `Raw` 이벤트는 포함되지 않으며, 필요하면 `subscribe_raw`를 사용합니다.
     pub fn subscribe(&self) -> broadcast::Receiver<Event> {
-        self.event_tx.subscribe()
+        self.bus.subscribe()
+    }
+
+    /// 진단 이벤트(`Error`, `ParseError`, `Unknown`)만 수신하는 "수신기"를 얻습니다.
+    /// SOOP 프로토콜 변경으로 해석하지 못한 메시지를 감지하는 데 사용합니다.
+    pub fn subscribe_diagnostics(&self) -> broadcast::Receiver<Event> {
+        self.bus.subscribe_diagnostics()
+    }
+
+    /// 수신한 원본 패킷(`Event::Raw`)을 수신하는 "수신기"를 얻습니다.
+    /// Raw 이벤트는 구독자가 있을 때만 만들어지며, 연결이 완전히 종료되면 `Disconnected`가 전달됩니다.
+    pub fn subscribe_raw(&self) -> broadcast::Receiver<Event> {
+        self.bus.subscribe_raw()
+    }
+
+    /// 필터를 통과한 이벤트만 받는 스트림을 얻습니다.
+    pub fn subscribe_filtered(&self, filter: EventFilter) -> EventStream<Event> {
+        self.bus.subscribe_filtered(filter, None, Some)
+    }
+
+    /// 이벤트를 버리지 않는 필터 구독 스트림을 얻습니다.
+    ///
+    /// 스트림의 큐가 가득 차면 자리가 날 때까지 연결 루프가 최대 `max_block`만큼 기다립니다.
+    /// 그 동안 채팅 서버의 메시지를 읽지 않으므로, 다른 구독자에게도 이벤트가 늦게 전달됩니다.
+    /// `max_block`이 지나도 자리가 나지 않으면 밀린 이벤트를 버리고 `Lagged`로 알립니다.
+    pub fn subscribe_lossless(
+        &self,
+        filter: EventFilter,
+        max_block: Duration,
+    ) -> EventStream<Event> {
+        self.bus.subscribe_filtered(filter, Some(max_block), Some)
+    }
+
+    /// 지금까지 발생한 이벤트 수와, 구독자가 밀려 버려진 이벤트 수
+    pub fn event_stats(&self) -> EventStats {
+        self.bus.stats()
+    }
+
+    /// 후원 이벤트만 받는 스트림을 얻습니다.
+    pub fn subscribe_donations(&self) -> EventStream<DonationEvent> {
+        self.bus.subscribe_donations()
+    }
+
+    /// 채팅 이벤트만 받는 스트림을 얻습니다.
+    pub fn subscribe_chats(&self) -> EventStream<ChatEvent> {
+        self.bus.subscribe_chats()
+    }
+}
+
+/// 채팅 서버 접속 주소를 만듭니다. 옵션으로 지정한 주소가 있으면 그대로 사용합니다.
+fn make_connection_url(
+    live_detail: &LiveDetail,
+    streamer_id: &str,
+    url_override: Option<&str>,
+) -> String {
+    if let Some(url) = url_override {
+        return url.to_string();
     }
+
+    format!(
+        "wss://{}:{}/Websocket/{}",
+        live_detail.ch_domain.to_lowercase(),
+        live_detail.ch_pt + 1,
+        streamer_id
+    )
 }
 
-// 장애 대응 로직은 SRP에 부합하지 않으므로 제거합니다.
+/// 로그인 정보가 있으면 로그인하여 채팅 인증 티켓을 반환합니다.
+async fn authenticate(
+    client: &SoopHttpClient,
+    login: Option<&SoopLoginOptions>,
+) -> Result<Option<String>> {
+    match login {
+        Some(login) => {
+            let auth = client.login(&login.user_id, &login.password).await?;
+            Ok(Some(auth.ticket))
+        }
+        None => Ok(None),
+    }
+}
 
 // --- 메인 로직 ---
 async fn run_connection_loop(mut state: ConnectionLoopState) {
-    // 세션 결과를 바탕으로 다음 행동을 결정합니다.
-    match try_connect_and_run_session(&mut state).await {
-        // 세션이 정상적으로 종료(Shutdown)되면, 메인 루프를 완전히 빠져나갑니다.
-        Ok(_) => {
-            state.event_tx.send(Event::Disconnected).ok();
+    // watch 모드에서는 시작 시점의 방송 상태를 먼저 알립니다.
+    if state.watch.is_some() {
+        if state.live_detail.is_some() {
+            state.emit_stream_online();
+        } else {
+            state.set_offline();
         }
-        Err(e) => {
-            // 그 외 모든 에러(네트워크, WebSocket 등)는 재연결을 시도합니다.
-            print!("[System] Connection error: {:?}. Retrying...\n", e);
+    }
+
+    loop {
+        // watch 모드에서 방송 중이 아니면, 방송이 시작될 때까지 기다립니다.
+        if state.live_detail.is_none() && !wait_until_live(&mut state).await {
+            break;
+        }
+
+        // 세션 결과를 바탕으로 다음 행동을 결정합니다.
+        let result = try_connect_and_run_session(&mut state).await;
+        state.backoff.disconnected();
+        match result {
+            // 세션이 정상적으로 종료(Shutdown)되면, 메인 루프를 완전히 빠져나갑니다.
+            Ok(_) => break,
+            // 방송이 종료되었으면 다시 방송을 기다립니다.
+            // 연결 장애가 아니므로 재연결 상태를 초기화합니다.
+            Err(Error::StreamOffline) if state.watch.is_some() => {
+                state.backoff.reset();
+                state.set_offline();
+                continue;
+            }
+            // 방송이 다시 시작되어 채팅방이 바뀌었으면 바로 새 채팅방에 접속합니다.
+            Err(Error::ChatRoomChanged) => continue,
+            Err(e) => {
+                // 그 외 모든 에러(네트워크, WebSocket 등)는 재연결을 시도합니다.
+                state.report_error("채팅 서버 연결 오류", &e);
+            }
+        }
+
+        // 재연결 정책에 따라 대기하고, 그 사이 바뀌었을 수 있는 채팅 서버 정보를 갱신합니다.
+        if !wait_for_reconnect(&mut state).await {
+            break;
         }
     }
+
+    state.emit(Event::Disconnected(DisconnectedEvent {
+        meta: EventMeta::new(Utc::now()),
+    }));
+    state.bus.close().await;
+}
+
+/// 재연결 가능한 상태가 될 때까지 대기합니다.
+/// true면 재연결을 시도하고, false면 재연결을 포기(또는 Shutdown)합니다.
+async fn wait_for_reconnect(state: &mut ConnectionLoopState) -> bool {
+    loop {
+        let Some((attempt, delay)) = state.backoff.next_delay() else {
+            return false;
+        };
+
+        state.emit(Event::Reconnecting(ReconnectingEvent {
+            meta: EventMeta::new(Utc::now()),
+            attempt,
+            delay,
+        }));
+
+        if wait_or_shutdown(state, delay).await {
+            return false;
+        }
+
+        match refresh_live_detail(state).await {
+            Ok(_) => return true,
+            // watch 모드에서는 재연결을 멈추고 방송 대기로 돌아갑니다.
+            Err(Error::StreamOffline) if state.watch.is_some() => {
+                state.set_offline();
+                return true;
+            }
+            Err(e) => state.report_error("방송 정보 갱신 실패", &e),
+        }
+    }
+}
+
+/// 방송이 시작될 때까지 주기적으로 방송 상태를 확인합니다.
+/// true면 방송이 시작된 것이고, false면 대기 중 Shutdown 명령을 받은 것입니다.
+async fn wait_until_live(state: &mut ConnectionLoopState) -> bool {
+    let poll_interval = state
+        .watch
+        .as_ref()
+        .map(|watch| watch.poll_interval)
+        .unwrap_or_default();
+
+    loop {
+        if wait_or_shutdown(state, poll_interval).await {
+            return false;
+        }
+
+        match refresh_live_detail(state).await {
+            Ok(_) => {
+                state.emit_stream_online();
+                return true;
+            }
+            Err(Error::StreamOffline) => {}
+            Err(e) => state.report_error("방송 상태 확인 실패", &e),
+        }
+    }
+}
+
+/// 연결 중에 방송 상태를 확인합니다.
+/// 방송이 끝났거나 새 방송으로 채팅방이 바뀌었으면 세션을 끝내기 위해 Err를 반환합니다.
+async fn check_stream(state: &mut ConnectionLoopState) -> Result<()> {
+    let (is_live, optional_live_detail) =
+        match state.client.get_live_detail_state(&state.streamer_id).await {
+            Ok(live_state) => live_state,
+            // 일시적인 API 오류로 연결을 끊지는 않습니다.
+            Err(e) => {
+                state.report_error("방송 상태 확인 실패", &e);
+                return Ok(());
+            }
+        };
+
+    let live_detail = match optional_live_detail {
+        Some(live_detail) if is_live => live_detail,
+        _ => return Err(Error::StreamOffline),
+    };
+
+    let current_chat_no = state.live_detail.as_ref().map(|d| d.ch_no.as_str());
+    if current_chat_no == Some(live_detail.ch_no.as_str()) {
+        return Ok(());
+    }
+
+    // 방송이 재시작되었습니다. 이전 방송을 종료 처리하고 새 방송 정보로 바꿉니다.
+    state.set_offline();
+    apply_live_detail(state, live_detail).await?;
+    state.emit_stream_online();
+    Err(Error::ChatRoomChanged)
+}
+
+/// 주어진 시간만큼 대기합니다. 대기 중 Shutdown 명령을 받으면 true를 반환합니다.
+async fn wait_or_shutdown(state: &mut ConnectionLoopState, delay: Duration) -> bool {
+    until_shutdown(state, tokio::time::sleep(delay))
+        .await
+        .is_none()
+}
+
+/// 연결이 없는 동안 `future`를 기다리며 명령을 처리합니다.
+/// 대기 중 Shutdown 명령을 받으면 None을 반환합니다.
+async fn until_shutdown<F: Future>(
+    state: &mut ConnectionLoopState,
+    future: F,
+) -> Option<F::Output> {
+    tokio::pin!(future);
+
+    loop {
+        tokio::select! {
+            output = &mut future => return Some(output),
+            request = state.command_rx.recv() => match request {
+                Some(CommandRequest { command: Command::Shutdown, responder }) => {
+                    respond(responder, Ok(()));
+                    return None;
+                }
+                // 연결이 없는 동안의 다른 명령은 실패로 응답한다
+                Some(CommandRequest { responder, .. }) => {
+                    respond(responder, Err(Error::NotConnected));
+                }
+                None => return None,
+            },
+        }
+    }
+}
+
+/// 방송 정보를 다시 조회하여 접속 URL, 채팅방 정보와 인증 티켓을 갱신합니다.
+async fn refresh_live_detail(state: &mut ConnectionLoopState) -> Result<()> {
+    let (is_live, optional_live_detail) = state
+        .client
+        .get_live_detail_state(&state.streamer_id)
+        .await?;
+
+    let live_detail = match optional_live_detail {
+        Some(live_detail) if is_live => live_detail,
+        _ => return Err(Error::StreamOffline),
+    };
+
+    apply_live_detail(state, live_detail).await
+}
+
+/// 새 방송 정보로 접속 URL, 채팅방 정보와 인증 티켓을 갱신합니다.
+async fn apply_live_detail(state: &mut ConnectionLoopState, live_detail: LiveDetail) -> Result<()> {
+    // 세션이 만료되었을 수 있으므로 인증 티켓도 다시 발급받습니다.
+    state.auth_ticket = authenticate(&state.client, state.login.as_ref()).await?;
+    state.connection_url = make_connection_url(
+        &live_detail,
+        &state.streamer_id,
+        state.connection_url_override.as_deref(),
+    );
+    state.stamp.set_chat_no(&live_detail.ch_no);
+    state.live_detail = Some(live_detail);
+    Ok(())
 }
 
 /// 한 번의 완전한 연결 세션을 시도하고, 성공 또는 실패를 반환합니다.
 /// Ok(())는 정상적인 종료(Shutdown)를 의미합니다.
 async fn try_connect_and_run_session(state: &mut ConnectionLoopState) -> Result<()> {
+    let Some(live_detail) = state.live_detail.clone() else {
+        return Err(Error::StreamOffline);
+    };
+
+    // 동시 연결 수 제한이 있으면 슬롯을 얻을 때까지 기다립니다. 슬롯은 세션이 끝나면 반납됩니다.
+    let _slot = match state.session_slots.clone() {
+        Some(slots) => match until_shutdown(state, slots.acquire_owned()).await {
+            Some(slot) => slot.ok(),
+            None => return Ok(()),
+        },
+        None => None,
+    };
+
     // 1. WebSocket 접속 URL 생성
     let url = Url::parse(&state.connection_url)?;
-    print!("[System] Attempting to connect to WebSocket: {}\n", url);
 
     let mut request = url.into_client_request()?;
     request
         .headers_mut()
         .insert("Sec-WebSocket-Protocol", HeaderValue::from_static("chat"));
 
-    // 2. TLS 설정
-    let config = ClientConfig::builder()
-        .dangerous()
-        .with_custom_certificate_verifier(Arc::new(NoVerification)) // 필드가 없는 검증기 사용
-        .with_no_client_auth();
-
     // - WebSocket 연결 시도
     let (ws_stream, _) = connect_async_tls_with_config(
         request,
         None,
         true,
-        Some(tokio_tungstenite::Connector::Rustls(Arc::new(config))),
+        Some(tokio_tungstenite::Connector::Rustls(Arc::clone(
+            &state.tls_config,
+        ))),
     )
     .await
     .map_err(|e| Error::ConnectionFailed(e.to_string()))?;
 
-    // 이벤트 전송
-    state
-        .event_tx
-        .send(Event::Connected)
-        .map_err(|_| Error::InternalChannel("Event channel closed".into()))?;
+    // 재연결 상태는 연결이 충분히 유지된 뒤에 끊겼을 때만 초기화합니다.
+    state.backoff.connected();
+
+    // 이벤트 전송 (구독자가 없어도 연결은 유지합니다)
+    let connected = ConnectedEvent {
+        meta: EventMeta::new(Utc::now()),
+    };
+    let event = if state.has_connected {
+        Event::Reconnected(connected)
+    } else {
+        Event::Connected(connected)
+    };
+    state.has_connected = true;
+    state.emit(event);
 
     let (mut writer, mut reader) = ws_stream.split();
 
     // Formatter 인스턴스 생성
-    let formatter = ChatFormatter::new(state.live_detail.clone(), state.password.clone());
+    let formatter = ChatFormatter::new(
+        live_detail,
+        state.password.clone(),
+        state.auth_ticket.clone(),
+    );
 
     // 4. 초기 패킷 전송 (CONNECT)
     let connect_packet = formatter.format_message(MessageType::Connect);
@@ -204,36 +627,357 @@ async fn run_communication_loop(
     formatter: &ChatFormatter,
 ) -> Result<()> {
     let mut ping_interval = tokio::time::interval(Duration::from_secs(60));
+    // watch 모드에서는 연결 중에도 방송 종료와 재시작을 확인합니다.
+    let mut watch_interval = state.watch.as_ref().map(|watch| {
+        tokio::time::interval_at(Instant::now() + watch.poll_interval, watch.poll_interval)
+    });
+    let mut decoder = FrameDecoder::new();
+
+    // 방송 상태 변경 메시지는 방송 정보를 다시 조회해야 하므로 이 루프에서 처리합니다.
+    let (state_change_tx, mut state_change_rx) = mpsc::unbounded_channel();
+    // 서버의 응답을 기다리는 명령, 세션이 끝나면 함께 버려집니다.
+    let pending = PendingCommands::default();
 
-    let handler = MessageHandler::new(formatter, state.event_tx.clone(), state.command_tx.clone());
+    let handler = MessageHandler::new(
+        formatter,
+        state.bus.clone(),
+        state.command_tx.clone(),
+        state_change_tx,
+        state.stamp.clone(),
+        state.decoders.clone(),
+        pending.clone(),
+    );
 
     loop {
         tokio::select! {
             // WebSocket 메시지 수신
-            Some(msg_result) = reader.next() => {
-                let raw = msg_result?.into_data(); // 에러 발생 시 '?'가 Err를 반환하여 루프 종료
-                if let Some(resp) = handler.handle(raw)? {
-                    // Handle the response if needed, or remove this block if not used
-                    writer.send(Message::Binary(resp)).await?;
+            msg_result = reader.next() => {
+                // 닫힘 프레임 없이 스트림이 끝나도 재연결 대상으로 취급합니다.
+                let Some(msg_result) = msg_result else {
+                    return Err(Error::ConnectionClosed);
+                };
+                // 에러 발생 시 '?'가 Err를 반환하여 루프 종료
+                let data = match msg_result? {
+                    Message::Binary(data) => data,
+                    Message::Text(text) => text.into_bytes(),
+                    // 서버가 연결을 닫으면 재연결 대상으로 취급합니다.
+                    Message::Close(_) => return Err(Error::ConnectionClosed),
+                    _ => continue,
+                };
+                // 하나의 메시지에 여러 패킷이 있거나, 패킷이 나뉘어 올 수 있습니다.
+                for frame in decoder.decode(&data) {
+                    if let Some(resp) = handler.handle(frame)? {
+                        writer.send(Message::Binary(resp)).await?;
+                    }
                 }
+                // 무손실 구독자가 밀려 있으면 따라올 때까지 다음 메시지를 읽지 않습니다.
+                state.bus.flush().await;
             },
             // 사용자 커맨드 수신
-            Some(command) = state.command_rx.recv() => {
+            Some(CommandRequest { command, responder }) = state.command_rx.recv() => {
                 match command {
                     Command::Shutdown => {
+                        respond(responder, Ok(()));
                         // 정상 종료 신호이므로 Ok(())를 반환
                         return Ok(());
                     }
-                    _ => {
-                        // 다른 명령어는 모두 처리하지 않는다
+                    command => {
+                        if !formatter.is_authenticated() {
+                            // 인증되지 않은 세션에서는 채팅이나 관리 명령을 보낼 수 없다
+                            respond(responder, Err(Error::NotAuthenticated));
+                        } else if let Some(packet) = formatter.format_command(&command) {
+                            // 전송에 성공하면 서버의 응답을 기다립니다.
+                            let responder = send_command_packet(writer, packet, responder).await?;
+                            if let Some(responder) = responder {
+                                let user_id = state.login.as_ref().map(|l| l.user_id.as_str());
+                                match command.ack(user_id.unwrap_or_default()) {
+                                    Some(ack) => {
+                                        pending.push(ack, responder, state.command_timeout)
+                                    }
+                                    None => respond(Some(responder), Ok(())),
+                                }
+                            }
+                        }
                     }
                 }
             },
+            // 응답 시간이 지난 명령
+            _ = sleep_until(pending.next_deadline()) => {
+                pending.expire(Instant::now());
+            }
             // 주기적인 Ping 전송
             _ = ping_interval.tick() => {
                 let msg = formatter.format_message(MessageType::Ping);
                 writer.send(Message::Binary(msg)).await?;
             }
+            // 방송 상태 변경
+            Some(message) = state_change_rx.recv() => {
+                handle_state_change(state, message).await?;
+            }
+            // 주기적인 방송 상태 확인
+            _ = tick(&mut watch_interval) => {
+                check_stream(state).await?;
+            }
+        }
+    }
+}
+
+/// 방송 정보를 다시 조회하여 이전 정보와 비교한 BJStateChange 이벤트를 방송합니다.
+/// watch 모드에서 방송이 종료되었으면 세션을 끝내기 위해 Err를 반환합니다.
+async fn handle_state_change(state: &mut ConnectionLoopState, message: RawMessage) -> Result<()> {
+    let before = state.live_detail.as_ref().map(BroadcastInfo::from);
+
+    let live_state = match state.client.get_live_detail_state(&state.streamer_id).await {
+        Ok(live_state) => Some(live_state),
+        Err(e) => {
+            state.report_error("방송 정보 갱신 실패", &e);
+            None
         }
+    };
+
+    let current_chat_no = state.live_detail.as_ref().map(|d| d.ch_no.clone());
+    let (changes, after) = match live_state {
+        // 같은 방송의 정보가 바뀐 경우
+        Some((true, Some(live_detail))) if Some(&live_detail.ch_no) == current_chat_no.as_ref() => {
+            let after = BroadcastInfo::from(&live_detail);
+            let changes = before
+                .as_ref()
+                .map(|before| before.diff(&after))
+                .unwrap_or_default();
+            state.live_detail = Some(live_detail);
+            (changes, Some(after))
+        }
+        // 조회에 실패한 경우
+        None => (Vec::new(), None),
+        // 방송이 종료되었거나 다른 방송으로 바뀐 경우
+        Some(_) => (vec![BroadcastChange::Ended], None),
+    };
+
+    let ended = changes.contains(&BroadcastChange::Ended);
+    state.emit(Event::BJStateChange(BJStateChangeEvent {
+        meta: EventMeta::new(message.received_time),
+        changes,
+        before,
+        after,
+        body: message.body,
+    }));
+
+    if ended && state.watch.is_some() {
+        return Err(Error::StreamOffline);
+    }
+    Ok(())
+}
+
+/// interval이 있으면 다음 tick까지, 없으면 영원히 대기합니다.
+async fn tick(interval: &mut Option<Interval>) {
+    match interval {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+/// 기한이 있으면 그 시간까지, 없으면 영원히 대기합니다.
+async fn sleep_until(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending().await,
+    }
+}
+
+/// 명령 패킷을 전송하고, 서버의 응답을 기다릴 응답 채널을 돌려줍니다.
+/// 전송 실패는 명령의 응답 채널로 알리고, 연결 단절로 간주하여 Err를 반환합니다.
+async fn send_command_packet(
+    writer: &mut SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>,
+    packet: Vec<u8>,
+    responder: Option<CommandResponder>,
+) -> Result<Option<CommandResponder>> {
+    match writer.send(Message::Binary(packet)).await {
+        Ok(_) => Ok(responder),
+        Err(e) => {
+            respond(responder, Err(Error::CommandFailed(e.to_string())));
+            Err(e.into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SoopApiBaseUrls;
+    use crate::chat::options::SoopWatchOptions;
+    use crate::chat::reconnect::ReconnectPolicy;
+    use crate::client::RetryPolicy;
+    use crate::constants::PLAYER_LIVE_API_PATH;
+    use crate::test_support::{MockResponse, live_detail_json, spawn_http_server, spawn_ws_server};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// mock API 서버와 채팅 서버에 접속하는 연결을 만듭니다.
+    async fn connection<F, Fut>(reconnect: ReconnectPolicy, chat_server: F) -> SoopChatConnection
+    where
+        F: Fn(WebSocketStream<TcpStream>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let (api, _) = spawn_http_server(|request| {
+            if request.path.starts_with(PLAYER_LIVE_API_PATH) {
+                MockResponse::ok(live_detail_json("1"))
+            } else {
+                MockResponse::status(404)
+            }
+        })
+        .await;
+        let client = SoopHttpClient::with_base_urls(SoopApiBaseUrls {
+            live: api,
+            ..Default::default()
+        });
+        let options = SoopChatOptions {
+            streamer_id: "streamer".to_string(),
+            reconnect,
+            connection_url: Some(spawn_ws_server(chat_server).await),
+            ..Default::default()
+        };
+        SoopChatConnection::new(Arc::new(client), options).unwrap()
+    }
+
+    /// Disconnected까지의 이벤트를 모읍니다.
+    async fn collect_until_disconnected(mut events: broadcast::Receiver<Event>) -> Vec<Event> {
+        let mut collected = Vec::new();
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while let Ok(event) = events.recv().await {
+                let disconnected = matches!(event, Event::Disconnected(_));
+                collected.push(event);
+                if disconnected {
+                    break;
+                }
+            }
+        })
+        .await
+        .expect("연결이 종료되지 않았습니다");
+        collected
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_when_server_closes_right_after_accept() {
+        let reconnect = ReconnectPolicy {
+            initial_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(1),
+            jitter: 0.0,
+            max_attempts: Some(2),
+            ..ReconnectPolicy::default()
+        };
+        // 접속을 받자마자 끊는 서버
+        let connection = connection(reconnect, |ws_stream| async move { drop(ws_stream) }).await;
+        let events = connection.subscribe();
+        connection.start().await.unwrap();
+
+        let events = collect_until_disconnected(events).await;
+        let attempts: Vec<u32> = events
+            .iter()
+            .filter_map(|event| match event {
+                Event::Reconnecting(e) => Some(e.attempt),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(attempts, vec![1, 2]);
+        assert!(matches!(events[0], Event::Connected(_)));
+        // 끊길 때마다 연결 오류를 진단 이벤트로 알립니다.
+        let errors = events
+            .iter()
+            .filter(|event| matches!(event, Event::Error(e) if e.code.is_none()))
+            .count();
+        assert_eq!(errors, 3);
+    }
+
+    #[tokio::test]
+    async fn test_live_state_check_failure_is_reported() {
+        // 시작할 때는 방송 전이고, 이후 방송 상태 조회가 실패합니다.
+        let checks = AtomicUsize::new(0);
+        let (api, _) = spawn_http_server(move |_| match checks.fetch_add(1, Ordering::Relaxed) {
+            0 => MockResponse::ok(r#"{"CHANNEL":{"RESULT":0}}"#),
+            _ => MockResponse::status(500),
+        })
+        .await;
+        let client = SoopHttpClient::builder()
+            .base_urls(SoopApiBaseUrls {
+                live: api,
+                ..Default::default()
+            })
+            .retry_policy(RetryPolicy::disabled())
+            .build()
+            .unwrap();
+        let options = SoopChatOptions {
+            streamer_id: "streamer".to_string(),
+            watch: Some(SoopWatchOptions {
+                poll_interval: Duration::from_millis(10),
+            }),
+            ..Default::default()
+        };
+        let connection = SoopChatConnection::new(Arc::new(client), options).unwrap();
+        let mut diagnostics = connection.subscribe_diagnostics();
+        connection.start().await.unwrap();
+
+        let event = tokio::time::timeout(Duration::from_secs(5), diagnostics.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(event, Event::Error(e) if e.reason.starts_with("방송 상태 확인 실패")));
+        connection.command(Command::Shutdown).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_state_change_refresh_failure_is_reported() {
+        // 시작할 때만 방송 정보를 돌려주고, 방송 상태 변경 후의 조회는 실패합니다.
+        let checks = AtomicUsize::new(0);
+        let (api, _) = spawn_http_server(move |_| match checks.fetch_add(1, Ordering::Relaxed) {
+            0 => MockResponse::ok(live_detail_json("1")),
+            _ => MockResponse::status(500),
+        })
+        .await;
+        let chat_server = spawn_ws_server(|mut ws_stream| async move {
+            let body = "\x0c0\x0c";
+            let packet = format!("\x1b\t{:04}{:06}00{}", 7, body.len(), body);
+            ws_stream
+                .send(Message::Binary(packet.into_bytes()))
+                .await
+                .ok();
+            while ws_stream.next().await.is_some() {}
+        })
+        .await;
+        let client = SoopHttpClient::builder()
+            .base_urls(SoopApiBaseUrls {
+                live: api,
+                ..Default::default()
+            })
+            .retry_policy(RetryPolicy::disabled())
+            .build()
+            .unwrap();
+        let options = SoopChatOptions {
+            streamer_id: "streamer".to_string(),
+            connection_url: Some(chat_server),
+            ..Default::default()
+        };
+        let connection = SoopChatConnection::new(Arc::new(client), options).unwrap();
+        let mut diagnostics = connection.subscribe_diagnostics();
+        let mut events = connection.subscribe();
+        connection.start().await.unwrap();
+
+        let event = tokio::time::timeout(Duration::from_secs(5), diagnostics.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(event, Event::Error(e) if e.reason.starts_with("방송 정보 갱신 실패")));
+        // 바뀐 내용은 알 수 없지만 BJStateChange 이벤트는 전달됩니다.
+        let state_change = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                if let Ok(Event::BJStateChange(e)) = events.recv().await {
+                    return e;
+                }
+            }
+        })
+        .await
+        .unwrap();
+        assert!(state_change.changes.is_empty());
+        connection.command(Command::Shutdown).unwrap();
     }
 }
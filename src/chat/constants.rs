@@ -22,7 +22,11 @@ pub mod message_codes {
     pub const CHAT: MessageCode = 5;
     pub const BJ_STATE_CHANGE: MessageCode = 7;
     pub const MUTE: MessageCode = 8;
+    pub const KICK: MessageCode = 11;
     pub const ENTER_INFO: MessageCode = 12;
+    pub const MANAGER_CHANGE: MessageCode = 13;
+    pub const VIEWER_COUNT: MessageCode = 15;
+    pub const ICE_MODE: MessageCode = 19;
     pub const FREEZE: MessageCode = 21;
     pub const SLOW: MessageCode = 23;
     pub const MANAGER_CHAT: MessageCode = 26;
@@ -51,6 +55,47 @@ pub mod message_codes {
     pub const SUBSCRIPTION_GIFTED: MessageCode = 108;
     pub const QUICKVIEW_GIFTED: MessageCode = 45;
     pub const OGQ_GIFTED: MessageCode = 118;
+    // ranking
+    pub const TOP_FAN: MessageCode = 30;
+    pub const TOP_FAN_SUB: MessageCode = 35;
+    // poll & chat room settings
+    pub const POLL: MessageCode = 50;
+    pub const CHAT_BLOCK_MODE: MessageCode = 51;
+
+    /// SOOP이 보내는 것으로 알려져 있지만, 아직 타입이 있는 이벤트로 해석하지 않는 코드 목록입니다.
+    /// 이 코드들은 `Event::Unknown`으로 전달되며, `name`에 아래 이름이 채워집니다.
+    pub const UNPARSED_CODES: &[(MessageCode, &str)] = &[
+        (3, "QUIT_CHANNEL"),
+        (6, "SET_CHANNEL_NAME"),
+        (9, "DIRECT_CHAT"),
+        (10, "NOTICE"),
+        (KICK, "KICK"),
+        (14, "SET_NICKNAME"),
+        (16, "RELOAD_HOST"),
+        (17, "CLUB_COLOR"),
+        (22, "GET_ICE_MODE_RELAY"),
+        (24, "RELOAD_BURN_LEVEL"),
+        (25, "BLIND_KICK"),
+        (27, "APPEND_DATA"),
+        (28, "BASEBALL_EVENT"),
+        (29, "PAID_ITEM"),
+        (31, "SNS_MESSAGE"),
+        (32, "SNS_MODE"),
+        (53, "SET_BROAD_INFO"),
+        (54, "BAN_WORD"),
+        (58, "ADMIN_NOTICE"),
+        (77, "KICK_USER_LIST"),
+        (94, "TRANSLATION_STATE"),
+        (95, "TRANSLATION"),
+    ];
+
+    /// 알려진 미해석 코드의 이름을 반환합니다.
+    pub fn unparsed_name(code: MessageCode) -> Option<&'static str> {
+        UNPARSED_CODES
+            .iter()
+            .find(|(known, _)| *known == code)
+            .map(|(_, name)| *name)
+    }
 }
 
 pub mod chat_message_fields {
@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use crate::chat::constants::message_codes::{self, MessageCode};
+use crate::chat::events::Event;
+use crate::chat::parser::raw::RawMessage;
+use crate::error::{Error, Result};
+
+/// 사용자 정의 패킷 해석기입니다.
+///
+/// SDK가 해석하지 않는 메시지 코드를 직접 처리하거나, 기본 해석기를 대체할 때 사용합니다.
+/// 해석 결과로 기존 이벤트를 만들거나, `Event::Custom`에 JSON 값을 담아 보낼 수 있습니다.
+pub trait PacketDecoder: Send + Sync {
+    /// 이 해석기가 처리할 메시지 코드
+    fn code(&self) -> MessageCode;
+
+    /// 메시지를 이벤트로 변환합니다. `None`을 반환하면 이벤트를 보내지 않습니다.
+    fn decode(&self, raw: RawMessage) -> Option<Event>;
+}
+
+/// 연결 과정에 필요해 대체할 수 없는 메시지 코드
+const RESERVED_CODES: &[MessageCode] = &[message_codes::CONNECT, message_codes::BJ_STATE_CHANGE];
+
+/// 메시지 코드별 사용자 정의 해석기 목록입니다.
+/// 연결 루프와 공유되므로, 연결 중에 등록한 해석기도 바로 적용됩니다.
+#[derive(Clone, Default)]
+pub(crate) struct DecoderRegistry {
+    decoders: Arc<RwLock<HashMap<MessageCode, Arc<dyn PacketDecoder>>>>,
+}
+
+impl DecoderRegistry {
+    pub(crate) fn register(&self, decoder: Arc<dyn PacketDecoder>) -> Result<()> {
+        let code = decoder.code();
+        if RESERVED_CODES.contains(&code) {
+            return Err(Error::InvalidConfig(format!(
+                "메시지 코드 {code}는 연결에 사용되므로 해석기를 등록할 수 없습니다."
+            )));
+        }
+
+        self.decoders.write().unwrap().insert(code, decoder);
+        Ok(())
+    }
+
+    pub(crate) fn unregister(&self, code: MessageCode) -> bool {
+        self.decoders.write().unwrap().remove(&code).is_some()
+    }
+
+    pub(crate) fn get(&self, code: MessageCode) -> Option<Arc<dyn PacketDecoder>> {
+        self.decoders.read().unwrap().get(&code).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chat::events::{CustomEvent, EventMeta};
+
+    struct EchoDecoder(MessageCode);
+
+    impl PacketDecoder for EchoDecoder {
+        fn code(&self) -> MessageCode {
+            self.0
+        }
+
+        fn decode(&self, raw: RawMessage) -> Option<Event> {
+            Some(Event::Custom(CustomEvent {
+                meta: EventMeta::new(raw.received_time),
+                code: raw.code,
+                data: serde_json::json!(raw.body),
+            }))
+        }
+    }
+
+    #[test]
+    fn test_register_and_reserved_codes() {
+        let registry = DecoderRegistry::default();
+
+        registry.register(Arc::new(EchoDecoder(9))).unwrap();
+        assert!(registry.get(9).is_some());
+        assert!(matches!(
+            registry.register(Arc::new(EchoDecoder(message_codes::CONNECT))),
+            Err(Error::InvalidConfig(_))
+        ));
+
+        assert!(registry.unregister(9));
+        assert!(registry.get(9).is_none());
+    }
+}
@@ -1,6 +1,8 @@
 use chrono::{DateTime, Utc};
-use serde::{Serialize, Serializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
 
 fn serialize_arc_bytes<S>(data: &Arc<[u8]>, serializer: S) -> Result<S::Ok, S::Error>
 where
@@ -9,23 +11,78 @@ where
     serializer.serialize_bytes(data)
 }
 
+fn deserialize_arc_bytes<'de, D>(deserializer: D) -> Result<Arc<[u8]>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    // 바이트를 지원하는 형식(MessagePack, CBOR)과 배열로 기록하는 형식(JSON)을 모두 받습니다.
+    serde_bytes::ByteBuf::deserialize(deserializer).map(|bytes| bytes.into_vec().into())
+}
+
+fn deserialize_schema_version<'de, D>(deserializer: D) -> Result<u32, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let version = u32::deserialize(deserializer)?;
+    if version > EVENT_SCHEMA_VERSION {
+        return Err(serde::de::Error::custom(format!(
+            "지원하지 않는 이벤트 스키마 버전입니다: {version}"
+        )));
+    }
+    Ok(version)
+}
+
 use crate::chat::{
     constants::message_codes::MessageCode,
-    types::{ChatType, DonationType, Emoticon, GiftType, MissionType, User},
+    types::{
+        BroadcastChange, BroadcastInfo, ChatRoomSetting, ChatType, DonationType, Emoticon,
+        FreezeTarget, GiftType, MissionType, User, UserStatus,
+    },
 };
 
+fn deserialize_freeze_targets<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    // 이전 버전은 매니저를 "MANGER"로 기록했으므로 현재 이름으로 바꿔 읽습니다.
+    let targets = Vec::<String>::deserialize(deserializer)?;
+    Ok(targets
+        .into_iter()
+        .map(|target| match target.as_str() {
+            "MANGER" => FreezeTarget::Manager.as_str().to_string(),
+            _ => target,
+        })
+        .collect())
+}
+
 // --- 채팅 이벤트 ---
-#[derive(Debug, Clone, Serialize)]
+/// `{"type": 이벤트 이름, "payload": 내용}` 형태로 직렬화됩니다.
+///
+/// 이벤트에 메타 정보(`EventMeta`)가 추가되면서 이전 버전과 payload 형태가 바뀌었습니다.
+/// 이전 형식의 기록을 읽던 코드는 다음과 같이 옮겨야 합니다.
+/// - `Connected`, `Disconnected`, `Reconnected`, `BJStateChange`: payload가 없었으나 메타 정보 객체를 가집니다.
+/// - `Unknown`: payload가 메시지 코드 숫자에서 객체로 바뀌었으며, 코드는 `payload.code`에 있습니다.
+/// - `Raw`: payload가 바이트 배열에서 객체로 바뀌었으며, 데이터는 `payload.data`에 있습니다.
+/// - 그 외 이벤트: 기존 필드는 그대로이고 `streamer_id`, `chat_no`, `seq`가 추가됩니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", content = "payload")]
 pub enum Event {
     // --- 생명 주기 관련 이벤트 ---
     /// 최초 연결 성공 시 발생
-    Connected,
+    Connected(ConnectedEvent),
     /// 연결이 완전히 종료되었을 때 발생
-    Disconnected,
+    Disconnected(DisconnectedEvent),
+    /// 연결이 끊어져 재연결을 기다리는 중일 때 발생
+    Reconnecting(ReconnectingEvent),
+    /// 재연결에 성공했을 때 발생
+    Reconnected(ConnectedEvent),
+    /// 방송이 시작되었을 때 발생 (watch 모드)
+    StreamOnline(StreamOnlineEvent),
+    /// 방송이 종료되었을 때 발생 (watch 모드)
+    StreamOffline(StreamOfflineEvent),
 
     // --- 채팅 관련 이벤트 ---
-    BJStateChange,
+    BJStateChange(BJStateChangeEvent),
     /// 채팅 메시지가 수신되었을 때 발생합니다.
     Chat(ChatEvent),
     /// 후원 (텍스트, 영상, 애드벌룬)이 발생했을 때.
@@ -61,31 +118,408 @@ pub enum Event {
     /// 미션 결과
     BattleMissionResult(BattleMissionResultEvent),
     ChallengeMissionResult(ChallengeMissionResultEvent),
+    /// 메시지 본문을 해석하지 못했을 때 (필드 누락, 잘못된 값)
+    ParseError(ParseErrorEvent),
+    /// 메시지를 처리하지 못했거나 (잘못된 헤더, 미션 JSON 오류 등)
+    /// 연결 중 오류가 발생했을 때 (접속 실패, 방송 정보 조회 실패 등)
+    Error(ErrorEvent),
+    /// 입장 시 전달되는 사용자 정보
+    EnterInfo(EnterInfoEvent),
+    /// 매니저가 임명되거나 해임되었을 때
+    ManagerChange(ManagerChangeEvent),
+    /// 시청자 수 갱신
+    ViewerCount(ViewerCountEvent),
+    /// 열혈팬 순위 변경
+    Ranking(RankingEvent),
+    /// 투표 알림
+    Poll(PollEvent),
+    /// 채팅방 설정 변경 (채팅 금지, 얼리기)
+    ChatRoomSetting(ChatRoomSettingEvent),
+    /// 사용자 정의 해석기(`PacketDecoder`)가 만든 이벤트
+    Custom(CustomEvent),
     /// 알 수 없는 이벤트 타입
-    Unknown(MessageCode),
+    Unknown(UnknownEvent),
     // 슬로우 이벤트
     Slow(SlowEvent),
     /// 직접 처리
-    #[serde(serialize_with = "serialize_arc_bytes")]
-    Raw(Arc<[u8]>), // 원시 데이터로 처리할 수 있는 이벤트
+    Raw(RawEvent), // 원시 데이터로 처리할 수 있는 이벤트
 }
 
-#[derive(Debug, Clone, Serialize)]
+/// 이벤트 종류, 필터로 구독할 이벤트를 고를 때 사용합니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum EventKind {
+    Connected,
+    Disconnected,
+    Reconnecting,
+    Reconnected,
+    StreamOnline,
+    StreamOffline,
+    BJStateChange,
+    Chat,
+    Donation,
+    Subscribe,
+    Enter,
+    Exit,
+    Kick,
+    KickCancel,
+    Sticker,
+    Gift,
+    Mute,
+    Black,
+    Freeze,
+    Notification,
+    Join,
+    MissionDonation,
+    MissionTotal,
+    BattleMissionResult,
+    ChallengeMissionResult,
+    ParseError,
+    Error,
+    EnterInfo,
+    ManagerChange,
+    ViewerCount,
+    Ranking,
+    Poll,
+    ChatRoomSetting,
+    Custom,
+    Unknown,
+    Slow,
+    Raw,
+}
+
+impl Event {
+    /// 이벤트 종류
+    pub fn kind(&self) -> EventKind {
+        match self {
+            Event::Connected(_) => EventKind::Connected,
+            Event::Disconnected(_) => EventKind::Disconnected,
+            Event::Reconnecting(_) => EventKind::Reconnecting,
+            Event::Reconnected(_) => EventKind::Reconnected,
+            Event::StreamOnline(_) => EventKind::StreamOnline,
+            Event::StreamOffline(_) => EventKind::StreamOffline,
+            Event::BJStateChange(_) => EventKind::BJStateChange,
+            Event::Chat(_) => EventKind::Chat,
+            Event::Donation(_) => EventKind::Donation,
+            Event::Subscribe(_) => EventKind::Subscribe,
+            Event::Enter(_) => EventKind::Enter,
+            Event::Exit(_) => EventKind::Exit,
+            Event::Kick(_) => EventKind::Kick,
+            Event::KickCancel(_) => EventKind::KickCancel,
+            Event::Sticker(_) => EventKind::Sticker,
+            Event::Gift(_) => EventKind::Gift,
+            Event::Mute(_) => EventKind::Mute,
+            Event::Black(_) => EventKind::Black,
+            Event::Freeze(_) => EventKind::Freeze,
+            Event::Notification(_) => EventKind::Notification,
+            Event::Join(_) => EventKind::Join,
+            Event::MissionDonation(_) => EventKind::MissionDonation,
+            Event::MissionTotal(_) => EventKind::MissionTotal,
+            Event::BattleMissionResult(_) => EventKind::BattleMissionResult,
+            Event::ChallengeMissionResult(_) => EventKind::ChallengeMissionResult,
+            Event::ParseError(_) => EventKind::ParseError,
+            Event::Error(_) => EventKind::Error,
+            Event::EnterInfo(_) => EventKind::EnterInfo,
+            Event::ManagerChange(_) => EventKind::ManagerChange,
+            Event::ViewerCount(_) => EventKind::ViewerCount,
+            Event::Ranking(_) => EventKind::Ranking,
+            Event::Poll(_) => EventKind::Poll,
+            Event::ChatRoomSetting(_) => EventKind::ChatRoomSetting,
+            Event::Custom(_) => EventKind::Custom,
+            Event::Unknown(_) => EventKind::Unknown,
+            Event::Slow(_) => EventKind::Slow,
+            Event::Raw(_) => EventKind::Raw,
+        }
+    }
+
+    /// 이벤트를 일으킨 사용자 ID, 특정 사용자와 관련 없는 이벤트는 None
+    pub fn user_id(&self) -> Option<&str> {
+        match self {
+            Event::Chat(e) => Some(&e.user.id),
+            Event::Enter(e) | Event::Exit(e) | Event::Kick(e) => Some(&e.user.id),
+            Event::Mute(e) => Some(&e.user.id),
+            Event::KickCancel(e) | Event::Black(e) | Event::Join(e) => Some(&e.user_id),
+            Event::Donation(e) => Some(&e.from),
+            Event::Sticker(e) => Some(&e.from),
+            Event::MissionDonation(e) => Some(&e.from),
+            Event::Gift(e) => Some(&e.sender_id),
+            Event::Subscribe(e) => Some(&e.user_id),
+            Event::EnterInfo(e) => Some(&e.user_id),
+            Event::ManagerChange(e) => Some(&e.user_id),
+            _ => None,
+        }
+    }
+
+    /// 후원 금액, 후원 이벤트가 아니면 None
+    pub fn donation_amount(&self) -> Option<u32> {
+        match self {
+            Event::Donation(e) => Some(e.amount),
+            Event::MissionDonation(e) => Some(e.amount),
+            _ => None,
+        }
+    }
+
+    /// 프로토콜 변경을 감지하는 데 쓰이는 진단 이벤트인지 여부
+    pub fn is_diagnostic(&self) -> bool {
+        matches!(
+            self,
+            Event::Error(_) | Event::ParseError(_) | Event::Unknown(_)
+        )
+    }
+
+    /// 이벤트의 공통 메타 정보
+    pub fn meta(&self) -> &EventMeta {
+        match self {
+            Event::Connected(e) | Event::Reconnected(e) => &e.meta,
+            Event::Disconnected(e) => &e.meta,
+            Event::Reconnecting(e) => &e.meta,
+            Event::StreamOnline(e) => &e.meta,
+            Event::StreamOffline(e) => &e.meta,
+            Event::BJStateChange(e) => &e.meta,
+            Event::Chat(e) => &e.meta,
+            Event::Donation(e) => &e.meta,
+            Event::Subscribe(e) => &e.meta,
+            Event::Enter(e) | Event::Exit(e) | Event::Kick(e) => &e.meta,
+            Event::KickCancel(e) | Event::Black(e) | Event::Join(e) => &e.meta,
+            Event::Sticker(e) => &e.meta,
+            Event::Gift(e) => &e.meta,
+            Event::Mute(e) => &e.meta,
+            Event::Freeze(e) => &e.meta,
+            Event::Notification(e) => &e.meta,
+            Event::MissionDonation(e) => &e.meta,
+            Event::MissionTotal(e) => &e.meta,
+            Event::BattleMissionResult(e) => &e.meta,
+            Event::ChallengeMissionResult(e) => &e.meta,
+            Event::ParseError(e) => &e.meta,
+            Event::Error(e) => &e.meta,
+            Event::EnterInfo(e) => &e.meta,
+            Event::ManagerChange(e) => &e.meta,
+            Event::ViewerCount(e) => &e.meta,
+            Event::Ranking(e) => &e.meta,
+            Event::Poll(e) => &e.meta,
+            Event::ChatRoomSetting(e) => &e.meta,
+            Event::Custom(e) => &e.meta,
+            Event::Unknown(e) => &e.meta,
+            Event::Slow(e) => &e.meta,
+            Event::Raw(e) => &e.meta,
+        }
+    }
+
+    fn meta_mut(&mut self) -> &mut EventMeta {
+        match self {
+            Event::Connected(e) | Event::Reconnected(e) => &mut e.meta,
+            Event::Disconnected(e) => &mut e.meta,
+            Event::Reconnecting(e) => &mut e.meta,
+            Event::StreamOnline(e) => &mut e.meta,
+            Event::StreamOffline(e) => &mut e.meta,
+            Event::BJStateChange(e) => &mut e.meta,
+            Event::Chat(e) => &mut e.meta,
+            Event::Donation(e) => &mut e.meta,
+            Event::Subscribe(e) => &mut e.meta,
+            Event::Enter(e) | Event::Exit(e) | Event::Kick(e) => &mut e.meta,
+            Event::KickCancel(e) | Event::Black(e) | Event::Join(e) => &mut e.meta,
+            Event::Sticker(e) => &mut e.meta,
+            Event::Gift(e) => &mut e.meta,
+            Event::Mute(e) => &mut e.meta,
+            Event::Freeze(e) => &mut e.meta,
+            Event::Notification(e) => &mut e.meta,
+            Event::MissionDonation(e) => &mut e.meta,
+            Event::MissionTotal(e) => &mut e.meta,
+            Event::BattleMissionResult(e) => &mut e.meta,
+            Event::ChallengeMissionResult(e) => &mut e.meta,
+            Event::ParseError(e) => &mut e.meta,
+            Event::Error(e) => &mut e.meta,
+            Event::EnterInfo(e) => &mut e.meta,
+            Event::ManagerChange(e) => &mut e.meta,
+            Event::ViewerCount(e) => &mut e.meta,
+            Event::Ranking(e) => &mut e.meta,
+            Event::Poll(e) => &mut e.meta,
+            Event::ChatRoomSetting(e) => &mut e.meta,
+            Event::Custom(e) => &mut e.meta,
+            Event::Unknown(e) => &mut e.meta,
+            Event::Slow(e) => &mut e.meta,
+            Event::Raw(e) => &mut e.meta,
+        }
+    }
+}
+
+/// 이벤트를 JSON 등으로 저장할 때의 스키마 버전입니다.
+/// 필드 이름이나 태그가 바뀌어 이전 기록과 호환되지 않으면 올립니다.
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
+/// 스키마 버전을 함께 기록하는 저장용 이벤트입니다.
+///
+/// `{"version":1,"type":"Chat","payload":{...}}` 형태로 직렬화되며,
+/// 현재 버전보다 새로운 기록은 역직렬화하지 않습니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionedEvent {
+    #[serde(deserialize_with = "deserialize_schema_version")]
+    pub version: u32,
+    #[serde(flatten)]
+    pub event: Event,
+}
+
+impl From<Event> for VersionedEvent {
+    fn from(event: Event) -> Self {
+        Self {
+            version: EVENT_SCHEMA_VERSION,
+            event,
+        }
+    }
+}
+
+/// 모든 이벤트에 공통으로 붙는 메타 정보입니다.
+///
+/// 이벤트 payload에 펼쳐져 직렬화되며, 필드 이름은 `received_time`과 같이 snake_case를 유지합니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EventMeta {
-    /// 이벤트가 라이브러리에서 생성된 시간
+    /// 이벤트가 라이브러리에서 생성된 시간 (VOD 채팅에서는 이벤트가 발생한 시간)
     pub received_time: DateTime<Utc>,
+    /// 이벤트가 발생한 채널의 스트리머 ID (VOD 채팅에서는 비어 있음)
+    pub streamer_id: String,
+    /// 이벤트가 발생한 채팅방 번호 (`LiveDetail::ch_no`)
+    pub chat_no: String,
+    /// 연결마다 1부터 증가하는 이벤트 순번, 재연결 후에도 이어집니다.
+    pub seq: u64,
+    /// VOD 시작부터 이벤트까지의 재생 시간(초), VOD 채팅에서만 채워집니다.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vod_offset: Option<f64>,
+}
+
+impl EventMeta {
+    /// 수신 시간만으로 메타 정보를 만듭니다.
+    /// 채널 정보와 순번은 연결에서 이벤트를 방송하기 직전에 채워집니다.
+    pub fn new(received_time: DateTime<Utc>) -> Self {
+        Self {
+            received_time,
+            streamer_id: String::new(),
+            chat_no: String::new(),
+            seq: 0,
+            vod_offset: None,
+        }
+    }
+}
+
+/// 한 연결에서 발생하는 이벤트에 채널 정보와 순번을 붙입니다.
+#[derive(Debug, Clone)]
+pub(crate) struct EventStamp {
+    streamer_id: String,
+    chat_no: String,
+    seq: Arc<AtomicU64>,
+}
+
+impl EventStamp {
+    pub fn new(streamer_id: &str, chat_no: &str) -> Self {
+        Self {
+            streamer_id: streamer_id.to_string(),
+            chat_no: chat_no.to_string(),
+            seq: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// 재연결로 채팅방이 바뀌었을 때 갱신합니다. 순번은 유지됩니다.
+    pub fn set_chat_no(&mut self, chat_no: &str) {
+        self.chat_no = chat_no.to_string();
+    }
+
+    pub fn stamp(&self, mut event: Event) -> Event {
+        let meta = event.meta_mut();
+        meta.streamer_id.clone_from(&self.streamer_id);
+        meta.chat_no.clone_from(&self.chat_no);
+        meta.seq = self.seq.fetch_add(1, Ordering::Relaxed) + 1;
+        event
+    }
 }
 
 // --- 생명 주기 관련 이벤트 ---
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConnectedEvent {
     #[serde(flatten)]
     pub meta: EventMeta,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisconnectedEvent {
+    #[serde(flatten)]
+    pub meta: EventMeta,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamOnlineEvent {
+    #[serde(flatten)]
+    pub meta: EventMeta,
+    pub title: String,
+    pub streamer_nick: String,
+    pub categories: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamOfflineEvent {
+    #[serde(flatten)]
+    pub meta: EventMeta,
+}
+
+/// 방송 제목, 카테고리, 비밀번호/연령 제한 설정이 바뀌었거나 방송이 종료되었을 때의 이벤트입니다.
+/// 패킷만으로는 바뀐 내용을 알 수 없어, 방송 정보를 다시 조회하여 이전 정보와 비교합니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BJStateChangeEvent {
+    #[serde(flatten)]
+    pub meta: EventMeta,
+    /// 바뀐 항목, 방송 정보를 다시 가져오지 못했다면 비어 있습니다.
+    pub changes: Vec<BroadcastChange>,
+    /// 변경 전 방송 정보
+    pub before: Option<BroadcastInfo>,
+    /// 변경 후 방송 정보, 방송이 종료되었거나 가져오지 못했다면 None
+    pub after: Option<BroadcastInfo>,
+    /// 패킷 본문
+    pub body: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomEvent {
+    #[serde(flatten)]
+    pub meta: EventMeta,
+    pub code: MessageCode,
+    pub data: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnknownEvent {
+    #[serde(flatten)]
+    pub meta: EventMeta,
+    pub code: MessageCode,
+    /// 알려진 미해석 코드라면 그 이름 (`message_codes::UNPARSED_CODES`)
+    pub name: Option<String>,
+    /// 패킷 본문
+    pub body: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RawEvent {
+    #[serde(flatten)]
+    pub meta: EventMeta,
+    #[serde(
+        serialize_with = "serialize_arc_bytes",
+        deserialize_with = "deserialize_arc_bytes"
+    )]
+    pub data: Arc<[u8]>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconnectingEvent {
+    #[serde(flatten)]
+    pub meta: EventMeta,
+    /// 재연결 시도 번호 (1부터 시작)
+    pub attempt: u32,
+    /// 다음 시도까지의 대기 시간
+    pub delay: Duration,
+}
+
 // --- 채팅 관련 이벤트 ---
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ChatEvent {
     /// 공통 속성 영역
@@ -100,7 +534,7 @@ pub struct ChatEvent {
     pub emoticon: Option<Emoticon>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GiftEvent {
     /// 공통 속성 영역
@@ -114,7 +548,7 @@ pub struct GiftEvent {
     pub gift_code: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DonationEvent {
     #[serde(flatten)]
@@ -127,7 +561,7 @@ pub struct DonationEvent {
     pub donation_type: DonationType,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StickerEvent {
     #[serde(flatten)]
@@ -138,7 +572,7 @@ pub struct StickerEvent {
     pub supporter_ordinal: u32, // 서포터 순번
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MissionEvent {
     #[serde(flatten)]
@@ -149,7 +583,7 @@ pub struct MissionEvent {
     pub mission_type: MissionType,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MissionTotalEvent {
     #[serde(flatten)]
@@ -158,7 +592,7 @@ pub struct MissionTotalEvent {
     pub amount: u32,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ChallengeMissionResultEvent {
     #[serde(flatten)]
@@ -167,7 +601,7 @@ pub struct ChallengeMissionResultEvent {
     pub title: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BattleMissionResultEvent {
     #[serde(flatten)]
@@ -177,7 +611,7 @@ pub struct BattleMissionResultEvent {
     pub title: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SubscribeEvent {
     #[serde(flatten)]
@@ -189,7 +623,7 @@ pub struct SubscribeEvent {
     pub renew: u32,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NotificationEvent {
     #[serde(flatten)]
     pub meta: EventMeta,
@@ -197,7 +631,7 @@ pub struct NotificationEvent {
     pub show: bool,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserEvent {
     #[serde(flatten)]
     pub meta: EventMeta,
@@ -205,7 +639,7 @@ pub struct UserEvent {
     pub user: User,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SimplifiedUserEvent {
     #[serde(flatten)]
@@ -213,7 +647,7 @@ pub struct SimplifiedUserEvent {
     pub user_id: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FreezeEvent {
     #[serde(flatten)]
@@ -221,10 +655,12 @@ pub struct FreezeEvent {
     pub freezed: bool,
     pub limit_subscription_month: u32,
     pub limit_balloons: u32,
+    /// 대상 이름(`FreezeTarget::as_str`), 매니저는 이전 버전에서 "MANGER"였으며 "MANAGER"로 바뀌었습니다.
+    #[serde(deserialize_with = "deserialize_freeze_targets")]
     pub targets: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MuteEvent {
     #[serde(flatten)]
@@ -237,9 +673,480 @@ pub struct MuteEvent {
     pub superuser_type: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParseErrorEvent {
+    #[serde(flatten)]
+    pub meta: EventMeta,
+    pub code: MessageCode,
+    /// 해석에 실패한 필드 이름
+    pub field: String,
+    pub body_len: usize,
+    /// 수신한 원본 메시지
+    #[serde(
+        serialize_with = "serialize_arc_bytes",
+        deserialize_with = "deserialize_arc_bytes"
+    )]
+    pub raw: Arc<[u8]>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorEvent {
+    #[serde(flatten)]
+    pub meta: EventMeta,
+    /// 헤더를 해석하지 못했거나 메시지와 관계없는 연결 오류인 경우 None
+    pub code: Option<MessageCode>,
+    /// 처리하지 못한 이유
+    pub reason: String,
+    /// 수신한 원본 메시지, 연결 오류인 경우 비어 있습니다.
+    #[serde(
+        serialize_with = "serialize_arc_bytes",
+        deserialize_with = "deserialize_arc_bytes"
+    )]
+    pub raw: Arc<[u8]>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SlowEvent {
     #[serde(flatten)]
     pub meta: EventMeta,
     pub duration: u32,
 }
+
+// --- 채팅방 정보 관련 이벤트 ---
+// 본문 구조가 공개되어 있지 않아, 확인된 필드만 해석하고 원본 본문을 함께 전달합니다.
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnterInfoEvent {
+    #[serde(flatten)]
+    pub meta: EventMeta,
+    pub user_id: String,
+    /// 사용자 상태, 플래그를 해석하지 못하면 None
+    pub status: Option<UserStatus>,
+    pub body: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManagerChangeEvent {
+    #[serde(flatten)]
+    pub meta: EventMeta,
+    pub user_id: String,
+    /// true면 임명, false면 해임
+    pub is_manager: bool,
+    pub body: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViewerCountEvent {
+    #[serde(flatten)]
+    pub meta: EventMeta,
+    pub count: u32,
+    pub body: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RankingEvent {
+    #[serde(flatten)]
+    pub meta: EventMeta,
+    /// 구독 열혈팬 순위인지 여부
+    pub is_subscription: bool,
+    /// 순위 순서대로 정렬된 사용자 ID
+    pub user_ids: Vec<String>,
+    pub body: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PollEvent {
+    #[serde(flatten)]
+    pub meta: EventMeta,
+    /// 본문이 JSON이면 해석된 값
+    pub poll: Option<serde_json::Value>,
+    pub body: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatRoomSettingEvent {
+    #[serde(flatten)]
+    pub meta: EventMeta,
+    pub setting: ChatRoomSetting,
+    pub enabled: bool,
+    pub body: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chat::types::UserSubscribe;
+    use std::collections::HashSet;
+
+    fn meta() -> EventMeta {
+        EventMeta {
+            received_time: Utc::now(),
+            streamer_id: "streamer".to_string(),
+            chat_no: "1".to_string(),
+            seq: 7,
+            vod_offset: None,
+        }
+    }
+
+    fn user() -> User {
+        User {
+            id: "user".to_string(),
+            label: "닉네임".to_string(),
+            status: UserStatus {
+                follow: 1,
+                is_bj: false,
+                is_manager: true,
+                is_top_fan: false,
+                is_fan: true,
+                is_supporter: false,
+            },
+            subscribe: Some(UserSubscribe { acc: 3, current: 2 }),
+        }
+    }
+
+    fn simplified() -> SimplifiedUserEvent {
+        SimplifiedUserEvent {
+            meta: meta(),
+            user_id: "user".to_string(),
+        }
+    }
+
+    fn user_event() -> UserEvent {
+        UserEvent {
+            meta: meta(),
+            user: user(),
+        }
+    }
+
+    fn body() -> Vec<String> {
+        vec!["a".to_string(), "".to_string()]
+    }
+
+    // 새 변형을 추가하면 컴파일 에러가 나도록 모든 변형을 나열합니다.
+    fn variant(event: &Event) -> usize {
+        match event {
+            Event::Connected(_) => 0,
+            Event::Disconnected(_) => 1,
+            Event::Reconnecting(_) => 2,
+            Event::Reconnected(_) => 3,
+            Event::StreamOnline(_) => 4,
+            Event::StreamOffline(_) => 5,
+            Event::BJStateChange(_) => 6,
+            Event::Chat(_) => 7,
+            Event::Donation(_) => 8,
+            Event::Subscribe(_) => 9,
+            Event::Enter(_) => 10,
+            Event::Exit(_) => 11,
+            Event::Kick(_) => 12,
+            Event::KickCancel(_) => 13,
+            Event::Sticker(_) => 14,
+            Event::Gift(_) => 15,
+            Event::Mute(_) => 16,
+            Event::Black(_) => 17,
+            Event::Freeze(_) => 18,
+            Event::Notification(_) => 19,
+            Event::Join(_) => 20,
+            Event::MissionDonation(_) => 21,
+            Event::MissionTotal(_) => 22,
+            Event::BattleMissionResult(_) => 23,
+            Event::ChallengeMissionResult(_) => 24,
+            Event::ParseError(_) => 25,
+            Event::Error(_) => 26,
+            Event::EnterInfo(_) => 27,
+            Event::ManagerChange(_) => 28,
+            Event::ViewerCount(_) => 29,
+            Event::Ranking(_) => 30,
+            Event::Poll(_) => 31,
+            Event::ChatRoomSetting(_) => 32,
+            Event::Custom(_) => 33,
+            Event::Unknown(_) => 34,
+            Event::Slow(_) => 35,
+            Event::Raw(_) => 36,
+        }
+    }
+    const VARIANT_COUNT: usize = 37;
+
+    fn samples() -> Vec<Event> {
+        let info = BroadcastInfo {
+            title: "제목".to_string(),
+            categories: vec!["게임".to_string()],
+            is_password: false,
+            is_adult: true,
+        };
+        let raw: Arc<[u8]> = Arc::from(&b"\x1b\t0005000003\x0c\xff\x0c"[..]);
+
+        vec![
+            Event::Connected(ConnectedEvent { meta: meta() }),
+            Event::Disconnected(DisconnectedEvent { meta: meta() }),
+            Event::Reconnecting(ReconnectingEvent {
+                meta: meta(),
+                attempt: 2,
+                delay: Duration::from_millis(1500),
+            }),
+            Event::Reconnected(ConnectedEvent { meta: meta() }),
+            Event::StreamOnline(StreamOnlineEvent {
+                meta: meta(),
+                title: "제목".to_string(),
+                streamer_nick: "스트리머".to_string(),
+                categories: vec!["게임".to_string()],
+            }),
+            Event::StreamOffline(StreamOfflineEvent { meta: meta() }),
+            Event::BJStateChange(BJStateChangeEvent {
+                meta: meta(),
+                changes: vec![BroadcastChange::Title, BroadcastChange::Ended],
+                before: Some(info),
+                after: None,
+                body: body(),
+            }),
+            Event::Chat(ChatEvent {
+                meta: meta(),
+                comment: "안녕하세요".to_string(),
+                chat_type: ChatType::Emoticon,
+                user: user(),
+                is_admin: false,
+                emoticon: Some(Emoticon {
+                    id: "1".to_string(),
+                    number: "2".to_string(),
+                    ext: "png".to_string(),
+                    version: "3".to_string(),
+                }),
+            }),
+            Event::Donation(DonationEvent {
+                meta: meta(),
+                from: "user".to_string(),
+                from_label: "닉네임".to_string(),
+                amount: 100,
+                fan_club_ordinal: 3,
+                become_top_fan: true,
+                donation_type: DonationType::ADBalloon,
+            }),
+            Event::Subscribe(SubscribeEvent {
+                meta: meta(),
+                user_id: "user".to_string(),
+                label: "닉네임".to_string(),
+                tier: 2,
+                renew: 5,
+            }),
+            Event::Enter(user_event()),
+            Event::Exit(user_event()),
+            Event::Kick(user_event()),
+            Event::KickCancel(simplified()),
+            Event::Sticker(StickerEvent {
+                meta: meta(),
+                from: "user".to_string(),
+                from_label: "닉네임".to_string(),
+                amount: 10,
+                supporter_ordinal: 1,
+            }),
+            Event::Gift(GiftEvent {
+                meta: meta(),
+                gift_type: GiftType::OGQ,
+                sender_id: "a".to_string(),
+                sender_label: "A".to_string(),
+                receiver_id: "b".to_string(),
+                receiver_label: "B".to_string(),
+                gift_code: "code".to_string(),
+            }),
+            Event::Mute(MuteEvent {
+                meta: meta(),
+                user: user(),
+                seconds: 30,
+                message: "mute".to_string(),
+                by: "manager".to_string(),
+                counts: 1,
+                superuser_type: "1".to_string(),
+            }),
+            Event::Black(simplified()),
+            Event::Freeze(FreezeEvent {
+                meta: meta(),
+                freezed: true,
+                limit_subscription_month: 1,
+                limit_balloons: 100,
+                targets: vec!["fan".to_string()],
+            }),
+            Event::Notification(NotificationEvent {
+                meta: meta(),
+                message: "공지".to_string(),
+                show: true,
+            }),
+            Event::Join(simplified()),
+            Event::MissionDonation(MissionEvent {
+                meta: meta(),
+                from: "user".to_string(),
+                from_label: "닉네임".to_string(),
+                amount: 1000,
+                mission_type: MissionType::Battle,
+            }),
+            Event::MissionTotal(MissionTotalEvent {
+                meta: meta(),
+                mission_type: MissionType::Challenge,
+                amount: 5000,
+            }),
+            Event::BattleMissionResult(BattleMissionResultEvent {
+                meta: meta(),
+                is_draw: false,
+                winner: "a".to_string(),
+                title: "대결".to_string(),
+            }),
+            Event::ChallengeMissionResult(ChallengeMissionResultEvent {
+                meta: meta(),
+                is_success: true,
+                title: "도전".to_string(),
+            }),
+            Event::ParseError(ParseErrorEvent {
+                meta: meta(),
+                code: 5,
+                field: "flags".to_string(),
+                body_len: 2,
+                raw: Arc::clone(&raw),
+            }),
+            Event::Error(ErrorEvent {
+                meta: meta(),
+                code: None,
+                reason: "invalid header".to_string(),
+                raw: Arc::clone(&raw),
+            }),
+            Event::EnterInfo(EnterInfoEvent {
+                meta: meta(),
+                user_id: "user".to_string(),
+                status: None,
+                body: body(),
+            }),
+            Event::ManagerChange(ManagerChangeEvent {
+                meta: meta(),
+                user_id: "user".to_string(),
+                is_manager: true,
+                body: body(),
+            }),
+            Event::ViewerCount(ViewerCountEvent {
+                meta: meta(),
+                count: 1234,
+                body: body(),
+            }),
+            Event::Ranking(RankingEvent {
+                meta: meta(),
+                is_subscription: false,
+                user_ids: vec!["a".to_string(), "b".to_string()],
+                body: body(),
+            }),
+            Event::Poll(PollEvent {
+                meta: meta(),
+                poll: Some(serde_json::json!({ "id": 1, "items": ["a", "b"] })),
+                body: body(),
+            }),
+            Event::ChatRoomSetting(ChatRoomSettingEvent {
+                meta: meta(),
+                setting: ChatRoomSetting::Ice,
+                enabled: true,
+                body: body(),
+            }),
+            Event::Custom(CustomEvent {
+                meta: meta(),
+                code: 9,
+                data: serde_json::json!({ "nested": { "value": null } }),
+            }),
+            Event::Unknown(UnknownEvent {
+                meta: meta(),
+                code: 10,
+                name: Some("NOTICE".to_string()),
+                body: body(),
+            }),
+            Event::Slow(SlowEvent {
+                meta: meta(),
+                duration: 3,
+            }),
+            Event::Raw(RawEvent {
+                meta: meta(),
+                data: raw,
+            }),
+        ]
+    }
+
+    #[test]
+    fn test_every_variant_round_trips() {
+        let samples = samples();
+        let covered: HashSet<usize> = samples.iter().map(variant).collect();
+        assert_eq!(covered.len(), VARIANT_COUNT);
+
+        for event in samples {
+            let json = serde_json::to_value(VersionedEvent::from(event.clone())).unwrap();
+            assert_eq!(json["version"], EVENT_SCHEMA_VERSION);
+
+            let decoded: VersionedEvent = serde_json::from_value(json.clone()).unwrap();
+            assert_eq!(variant(&decoded.event), variant(&event));
+            assert_eq!(serde_json::to_value(&decoded).unwrap(), json);
+
+            // 버전 없이 저장한 이벤트도 그대로 읽을 수 있어야 합니다.
+            let plain = serde_json::to_string(&event).unwrap();
+            let decoded: Event = serde_json::from_str(&plain).unwrap();
+            assert_eq!(serde_json::to_string(&decoded).unwrap(), plain);
+        }
+    }
+
+    #[test]
+    fn test_raw_bytes_round_trip_in_binary_format() {
+        let event = Event::Raw(RawEvent {
+            meta: meta(),
+            data: Arc::from(&b"\x1b\t0005"[..]),
+        });
+
+        let mut cbor = Vec::new();
+        ciborium::into_writer(&VersionedEvent::from(event), &mut cbor).unwrap();
+        let decoded: VersionedEvent = ciborium::from_reader(cbor.as_slice()).unwrap();
+
+        match decoded.event {
+            Event::Raw(e) => assert_eq!(&*e.data, b"\x1b\t0005"),
+            e => panic!("unexpected event: {e:?}"),
+        }
+    }
+
+    #[test]
+    fn test_meta_keeps_snake_case_keys() {
+        let json = serde_json::to_value(Event::Unknown(UnknownEvent {
+            meta: meta(),
+            code: 9999,
+            name: None,
+            body: Vec::new(),
+        }))
+        .unwrap();
+
+        assert!(json["payload"]["received_time"].is_string());
+        assert!(json["payload"]["streamer_id"].is_string());
+        assert_eq!(json["payload"]["code"], 9999);
+    }
+
+    #[test]
+    fn test_legacy_freeze_target_name_is_accepted() {
+        let mut json = serde_json::to_value(Event::Freeze(FreezeEvent {
+            meta: meta(),
+            freezed: true,
+            limit_subscription_month: 0,
+            limit_balloons: 0,
+            targets: vec![],
+        }))
+        .unwrap();
+        json["payload"]["targets"] = serde_json::json!(["FAN", "MANGER"]);
+
+        let Event::Freeze(event) = serde_json::from_value(json).unwrap() else {
+            panic!("Freeze 이벤트가 아닙니다.");
+        };
+        assert_eq!(event.targets, vec!["FAN", "MANAGER"]);
+    }
+
+    #[test]
+    fn test_newer_schema_version_is_rejected() {
+        let mut json =
+            serde_json::to_value(VersionedEvent::from(Event::Connected(ConnectedEvent {
+                meta: meta(),
+            })))
+            .unwrap();
+        json["version"] = (EVENT_SCHEMA_VERSION + 1).into();
+
+        assert!(serde_json::from_value::<VersionedEvent>(json).is_err());
+    }
+}
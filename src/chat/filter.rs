@@ -0,0 +1,204 @@
+use std::collections::HashSet;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures_util::Stream;
+use tokio::sync::mpsc;
+
+use crate::chat::events::{Event, EventKind};
+
+type Predicate = Arc<dyn Fn(&Event) -> bool + Send + Sync>;
+
+/// 구독할 이벤트를 고르는 조건입니다. 설정한 조건을 모두 만족하는 이벤트만 전달됩니다.
+///
+/// 조건 없이 만든 필터는 `Raw`를 제외한 모든 이벤트를 전달하며,
+/// `Raw`는 종류로 직접 지정한 경우에만 전달됩니다.
+///
+/// ```
+/// use soup_sdk::chat::{EventFilter, EventKind};
+///
+/// let filter = EventFilter::new()
+///     .kind(EventKind::Donation)
+///     .min_donation(1000);
+/// ```
+#[derive(Clone, Default)]
+pub struct EventFilter {
+    kinds: Option<HashSet<EventKind>>,
+    user_ids: Option<HashSet<String>>,
+    min_donation: Option<u32>,
+    predicate: Option<Predicate>,
+}
+
+impl EventFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 전달할 이벤트 종류를 추가합니다.
+    pub fn kind(self, kind: EventKind) -> Self {
+        self.kinds([kind])
+    }
+
+    /// 전달할 이벤트 종류를 여러 개 추가합니다.
+    pub fn kinds(mut self, kinds: impl IntoIterator<Item = EventKind>) -> Self {
+        self.kinds.get_or_insert_with(HashSet::new).extend(kinds);
+        self
+    }
+
+    /// 이 사용자가 일으킨 이벤트만 전달합니다. 여러 번 호출하면 그 중 하나와 일치하면 됩니다.
+    pub fn user_id(mut self, user_id: impl Into<String>) -> Self {
+        self.user_ids
+            .get_or_insert_with(HashSet::new)
+            .insert(user_id.into());
+        self
+    }
+
+    /// 후원 금액이 `amount` 이상인 후원 이벤트만 전달합니다.
+    pub fn min_donation(mut self, amount: u32) -> Self {
+        self.min_donation = Some(amount);
+        self
+    }
+
+    /// 직접 만든 조건을 추가합니다.
+    pub fn predicate(mut self, predicate: impl Fn(&Event) -> bool + Send + Sync + 'static) -> Self {
+        self.predicate = Some(Arc::new(predicate));
+        self
+    }
+
+    /// 이 종류의 이벤트를 받을 수 있는지 여부, 다른 조건은 확인하지 않습니다.
+    pub fn accepts_kind(&self, kind: EventKind) -> bool {
+        match &self.kinds {
+            Some(kinds) => kinds.contains(&kind),
+            None => kind != EventKind::Raw,
+        }
+    }
+
+    pub fn matches(&self, event: &Event) -> bool {
+        if !self.accepts_kind(event.kind()) {
+            return false;
+        }
+
+        if let Some(user_ids) = &self.user_ids
+            && !event.user_id().is_some_and(|id| user_ids.contains(id))
+        {
+            return false;
+        }
+
+        if let Some(min) = self.min_donation
+            && event.donation_amount().is_none_or(|amount| amount < min)
+        {
+            return false;
+        }
+
+        self.predicate
+            .as_ref()
+            .is_none_or(|predicate| predicate(event))
+    }
+}
+
+/// 구독자가 밀려 이벤트를 놓쳤을 때 스트림으로 전달되는 신호입니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("구독자가 밀려 이벤트 {skipped}개를 놓쳤습니다.")]
+pub struct Lagged {
+    /// 놓친 이벤트 수
+    pub skipped: u64,
+}
+
+/// 연결에서 발생한 이벤트 수와, 구독자가 밀려 버려진 이벤트 수입니다.
+///
+/// `dropped`는 필터 구독(`EventStream`)에서 버려진 이벤트만 셉니다.
+/// `subscribe`로 얻은 수신기가 놓친 이벤트는 `RecvError::Lagged`로 수신 측에서 확인합니다.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EventStats {
+    pub sent: u64,
+    pub dropped: u64,
+}
+
+/// 필터 구독 큐에 들어가는 항목
+pub(crate) enum Delivery {
+    Event(Box<Event>),
+    Lagged(u64),
+}
+
+/// 필터를 통과한 이벤트만 받는 스트림입니다.
+///
+/// 필터는 이벤트를 보내는 쪽에서 확인하므로, 관심 없는 이벤트로는 깨어나지 않습니다.
+/// 큐가 가득 차 이벤트를 놓치면, 그 다음 이벤트 전에 `Err(Lagged)`가 전달됩니다.
+/// 연결이 완전히 종료되면 스트림이 끝납니다.
+pub struct EventStream<T> {
+    rx: mpsc::Receiver<Delivery>,
+    map: fn(Event) -> Option<T>,
+}
+
+impl<T> EventStream<T> {
+    pub(crate) fn new(rx: mpsc::Receiver<Delivery>, map: fn(Event) -> Option<T>) -> Self {
+        Self { rx, map }
+    }
+
+    /// 다음 이벤트를 기다립니다.
+    pub async fn recv(&mut self) -> Option<Result<T, Lagged>> {
+        std::future::poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+}
+
+impl<T> Stream for EventStream<T> {
+    type Item = Result<T, Lagged>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match self.rx.poll_recv(cx) {
+                Poll::Ready(Some(Delivery::Event(event))) => {
+                    if let Some(item) = (self.map)(*event) {
+                        return Poll::Ready(Some(Ok(item)));
+                    }
+                }
+                Poll::Ready(Some(Delivery::Lagged(skipped))) => {
+                    return Poll::Ready(Some(Err(Lagged { skipped })));
+                }
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chat::events::{DonationEvent, EventMeta, RawEvent};
+    use crate::chat::types::DonationType;
+    use chrono::Utc;
+
+    fn donation(from: &str, amount: u32) -> Event {
+        Event::Donation(DonationEvent {
+            meta: EventMeta::new(Utc::now()),
+            from: from.to_string(),
+            from_label: String::new(),
+            amount,
+            fan_club_ordinal: 0,
+            become_top_fan: false,
+            donation_type: DonationType::Balloon,
+        })
+    }
+
+    #[test]
+    fn test_filter_conditions() {
+        let raw = Event::Raw(RawEvent {
+            meta: EventMeta::new(Utc::now()),
+            data: Arc::from(&b""[..]),
+        });
+        assert!(EventFilter::new().matches(&donation("a", 1)));
+        assert!(!EventFilter::new().matches(&raw));
+        assert!(EventFilter::new().kind(EventKind::Raw).matches(&raw));
+
+        let filter = EventFilter::new().user_id("a").min_donation(100);
+        assert!(filter.matches(&donation("a", 100)));
+        assert!(!filter.matches(&donation("a", 99)));
+        assert!(!filter.matches(&donation("b", 100)));
+
+        let filter = EventFilter::new().predicate(|e| e.donation_amount() == Some(7));
+        assert!(filter.matches(&donation("a", 7)));
+        assert!(!filter.matches(&donation("a", 8)));
+    }
+}
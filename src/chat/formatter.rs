@@ -1,7 +1,8 @@
 use crate::{
     chat::{
-        commands::MessageType,
-        constants::{ELEMENT_END, ELEMENT_START, SEPARATOR, SEPARATOR_3_TIMES, SPACE, STARTER_VEC},
+        commands::{Command, MessageType},
+        constants::{SEPARATOR, STARTER_VEC},
+        types::{FreezeTarget, SuperUser},
     },
     models::LiveDetail,
 };
@@ -11,16 +12,24 @@ use crate::{
 pub struct ChatFormatter {
     pub live_detail: LiveDetail,
     pub password: String,
+    // 로그인한 경우에만 할당되는 채팅 인증 티켓
+    pub auth_ticket: Option<String>,
 }
 
 impl ChatFormatter {
-    pub fn new(live_detail: LiveDetail, password: String) -> Self {
+    pub fn new(live_detail: LiveDetail, password: String, auth_ticket: Option<String>) -> Self {
         Self {
             live_detail,
             password,
+            auth_ticket,
         }
     }
 
+    /// 인증된 세션인지 여부
+    pub fn is_authenticated(&self) -> bool {
+        self.auth_ticket.is_some()
+    }
+
     pub fn format_message(&self, message_type: MessageType) -> Vec<u8> {
         let payload: String = match message_type {
             MessageType::Connect => self.format_connect_packet(),
@@ -28,21 +37,129 @@ impl ChatFormatter {
             _ => "".to_string(), // 다른 메시지 코드에 대한 기본값
         };
 
-        return bundle(message_type, payload.as_bytes());
+        bundle(message_type, payload.as_bytes())
+    }
+
+    /// 사용자 명령을 전송할 패킷으로 변환합니다.
+    /// 서버로 보낼 패킷이 없는 명령(Shutdown)은 None을 반환합니다.
+    pub fn format_command(&self, command: &Command) -> Option<Vec<u8>> {
+        let packet = match command {
+            Command::SendChat(message) => self.format_chat(message),
+            Command::Mute {
+                user_id,
+                seconds,
+                superuser_type,
+            } => format_mute(user_id, *seconds, *superuser_type),
+            Command::Kick { user_id } => {
+                bundle(MessageType::Kick, join_fields(&[user_id]).as_bytes())
+            }
+            Command::KickCancel { user_id } => bundle(
+                MessageType::KickCancel,
+                join_fields(&["1", user_id]).as_bytes(),
+            ),
+            Command::Freeze {
+                freezed,
+                targets,
+                limit_balloons,
+                limit_subscription_month,
+            } => format_freeze(
+                *freezed,
+                targets,
+                *limit_balloons,
+                *limit_subscription_month,
+            ),
+            Command::Slow { duration } => bundle(
+                MessageType::Slow,
+                join_fields(&[&duration.to_string()]).as_bytes(),
+            ),
+            Command::Notice { message, show } => {
+                let show = if *show { "1" } else { "0" };
+                let payload = join_fields(&["0", show, "0", &sanitize(message)]);
+                bundle(MessageType::Notice, payload.as_bytes())
+            }
+            Command::Shutdown => return None,
+        };
+
+        Some(packet)
+    }
+
+    /// 채팅 메시지 전송 패킷을 생성합니다.
+    pub fn format_chat(&self, message: &str) -> Vec<u8> {
+        let payload = join_fields(&[&sanitize(message), "0"]);
+
+        bundle(MessageType::Chat, payload.as_bytes())
     }
 
     fn format_connect_packet(&self) -> String {
-        format!("{}16{}", SEPARATOR_3_TIMES, SEPARATOR)
+        format!(
+            "{}{}{}{}16{}",
+            SEPARATOR,
+            self.auth_ticket.as_deref().unwrap_or_default(),
+            SEPARATOR,
+            SEPARATOR,
+            SEPARATOR
+        )
     }
 
     fn format_join_packet(&self) -> String {
         format!(
-            "\x0c{}\x0c\x0c0\x0c\x0clog\x11\x06&\x06set_bps\x06=\x068000\x06&\x06view_bps\x06=\x061000\x06&\x06quality\x06=\x06normal\x06&\x06uuid\x06=\x06\x06&\x06geo_cc\x06=\x06KR\x06&\x06geo_rc\x06=\x0626\x06&\x06acpt_lang\x06=\x06ko_KR\x06&\x06svc_lang\x06=\x06ko_KR\x06&\x06subscribe\x06=\x060\x06&\x06lowlatency\x06=\x060\x06&\x06mode\x06=\x06landing\x12pwd\x11{}\x12auth_info\x11NULL\x12pver\x112\x12access_system\x11html5\x12\x0c",
-            self.live_detail.ch_no, self.password
+            "\x0c{}\x0c\x0c0\x0c\x0clog\x11\x06&\x06set_bps\x06=\x068000\x06&\x06view_bps\x06=\x061000\x06&\x06quality\x06=\x06normal\x06&\x06uuid\x06=\x06\x06&\x06geo_cc\x06=\x06KR\x06&\x06geo_rc\x06=\x0626\x06&\x06acpt_lang\x06=\x06ko_KR\x06&\x06svc_lang\x06=\x06ko_KR\x06&\x06subscribe\x06=\x060\x06&\x06lowlatency\x06=\x060\x06&\x06mode\x06=\x06landing\x12pwd\x11{}\x12auth_info\x11{}\x12pver\x112\x12access_system\x11html5\x12\x0c",
+            self.live_detail.ch_no,
+            self.password,
+            self.auth_ticket.as_deref().unwrap_or("NULL")
         )
     }
 }
 
+/// 채팅 금지 패킷을 생성합니다. 필드 순서는 수신하는 MUTE 패킷과 같습니다.
+fn format_mute(user_id: &str, seconds: u32, superuser_type: SuperUser) -> Vec<u8> {
+    let payload = join_fields(&[
+        user_id,
+        "0",
+        &seconds.to_string(),
+        "1",
+        "",
+        &superuser_type.code().to_string(),
+    ]);
+    bundle(MessageType::Mute, payload.as_bytes())
+}
+
+/// 얼리기 패킷을 생성합니다. 필드 순서는 수신하는 FREEZE 패킷과 같습니다.
+fn format_freeze(
+    freezed: bool,
+    targets: &[FreezeTarget],
+    limit_balloons: u32,
+    limit_subscription_month: u32,
+) -> Vec<u8> {
+    let flags = targets
+        .iter()
+        .fold(0, |flags, target| flags | target.flag());
+
+    let payload = join_fields(&[
+        if freezed { "1" } else { "0" },
+        "0",
+        &flags.to_string(),
+        &limit_balloons.to_string(),
+        &limit_subscription_month.to_string(),
+    ]);
+    bundle(MessageType::Freeze, payload.as_bytes())
+}
+
+/// 필드들을 구분자로 이어 패킷 본문을 만듭니다. (앞뒤에 구분자가 붙습니다)
+fn join_fields(fields: &[&str]) -> String {
+    let mut payload = String::from(SEPARATOR);
+    for field in fields {
+        payload.push_str(field);
+        payload.push(SEPARATOR);
+    }
+    payload
+}
+
+/// 구분자가 포함되면 패킷이 깨지므로 공백으로 치환합니다.
+fn sanitize(text: &str) -> String {
+    text.replace(SEPARATOR, " ")
+}
+
 /// 여러 바이트 슬라이스 조각들을 하나의 새로운 Vec<u8>로 병합합니다.
 fn flatten_byte_slices(parts: &[&[u8]]) -> Vec<u8> {
     let total_len = parts.iter().map(|s| s.len()).sum();
@@ -88,3 +205,144 @@ pub fn bundle(message_type: MessageType, body: &[u8]) -> Vec<u8> {
     // 최종적으로 완성된 메시지 패킷을 반환합니다.
     header
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chat::constants::message_codes::{self, MessageCode};
+    use crate::chat::parser::{
+        freeze::parse_freeze_event, kick::parse_kick_cancel_event,
+        notification::parse_notification_event, raw::parse_message, slow::parse_slow_event,
+    };
+
+    fn formatter() -> ChatFormatter {
+        let live_detail = LiveDetail {
+            is_live: true,
+            ch_domain: "chat.sooplive.co.kr".to_string(),
+            ch_pt: 8000,
+            ch_no: "1".to_string(),
+            streamer_nick: "".to_string(),
+            title: "".to_string(),
+            categories: vec![],
+            is_password: false,
+            is_adult: false,
+        };
+        ChatFormatter::new(live_detail, "".to_string(), Some("ticket".to_string()))
+    }
+
+    #[test]
+    fn test_freeze_command_round_trip() {
+        let command = Command::Freeze {
+            freezed: true,
+            targets: vec![
+                FreezeTarget::Fan,
+                FreezeTarget::TopFan,
+                FreezeTarget::Manager,
+            ],
+            limit_balloons: 10,
+            limit_subscription_month: 3,
+        };
+
+        let packet = formatter().format_command(&command).unwrap();
+        let event = parse_freeze_event(parse_message(&packet).unwrap()).unwrap();
+
+        assert!(event.freezed);
+        assert_eq!(event.targets, vec!["FAN", "TOP_FAN", "MANAGER"]);
+        assert_eq!(event.limit_balloons, 10);
+        assert_eq!(event.limit_subscription_month, 3);
+    }
+
+    #[test]
+    fn test_join_packet_carries_auth_ticket() {
+        let join = formatter().format_message(MessageType::JOIN);
+        let message = parse_message(&join).unwrap();
+        assert_eq!(message.code, message_codes::JOIN);
+        assert_eq!(message.body[0], "1");
+        assert!(message.body[4].contains("\x12auth_info\x11ticket\x12"));
+
+        // 로그인하지 않은 세션은 NULL을 보냅니다.
+        let anonymous = ChatFormatter {
+            auth_ticket: None,
+            ..formatter()
+        };
+        let join = anonymous.format_message(MessageType::JOIN);
+        let message = parse_message(&join).unwrap();
+        assert!(message.body[4].contains("\x12auth_info\x11NULL\x12"));
+        assert!(!anonymous.is_authenticated());
+    }
+
+    #[test]
+    fn test_chat_packet() {
+        let packet = formatter().format_chat("안녕\x0c하세요");
+        assert_eq!(&packet[2..6], b"0005");
+        // 구분자는 공백으로 바뀌어 필드가 나뉘지 않습니다.
+        assert_eq!(&packet[14..], "\x0c안녕 하세요\x0c0\x0c".as_bytes());
+
+        let message = parse_message(&packet).unwrap();
+        assert_eq!(message.code, message_codes::CHAT);
+        assert_eq!(message.body[..2], ["안녕 하세요", "0"]);
+    }
+
+    /// 명령 패킷의 코드와 본문 필드
+    fn command_fields(command: Command) -> (MessageCode, Vec<String>) {
+        let packet = formatter().format_command(&command).unwrap();
+        let message = parse_message(&packet).unwrap();
+        (message.code, message.body)
+    }
+
+    #[test]
+    fn test_mute_and_kick_packets() {
+        let (code, body) = command_fields(Command::Mute {
+            user_id: "viewer".to_string(),
+            seconds: 30,
+            superuser_type: SuperUser::Operator,
+        });
+        assert_eq!(code, message_codes::MUTE);
+        assert_eq!(body, ["viewer", "0", "30", "1", "", "2", ""]);
+        assert_eq!(SuperUser::Operator.as_str(), "operator");
+
+        let (code, body) = command_fields(Command::Kick {
+            user_id: "viewer".to_string(),
+        });
+        assert_eq!(code, message_codes::KICK);
+        assert_eq!(body, ["viewer", ""]);
+
+        let packet = formatter()
+            .format_command(&Command::KickCancel {
+                user_id: "viewer".to_string(),
+            })
+            .unwrap();
+        let event = parse_kick_cancel_event(parse_message(&packet).unwrap()).unwrap();
+        assert_eq!(event.unwrap().user_id, "viewer");
+    }
+
+    #[test]
+    fn test_room_setting_packets() {
+        let packet = formatter()
+            .format_command(&Command::Slow { duration: 5 })
+            .unwrap();
+        let event = parse_slow_event(parse_message(&packet).unwrap()).unwrap();
+        assert_eq!(event.duration, 5);
+
+        let packet = formatter()
+            .format_command(&Command::Notice {
+                message: "공지\x0c사항".to_string(),
+                show: true,
+            })
+            .unwrap();
+        let event = parse_notification_event(parse_message(&packet).unwrap()).unwrap();
+        assert!(event.show);
+        assert_eq!(event.message, "공지 사항");
+
+        // 명령으로 보내는 채팅은 format_chat과 같은 패킷입니다.
+        assert_eq!(
+            formatter().format_command(&Command::SendChat("안녕".to_string())),
+            Some(formatter().format_chat("안녕"))
+        );
+    }
+
+    #[test]
+    fn test_shutdown_has_no_packet() {
+        assert!(formatter().format_command(&Command::Shutdown).is_none());
+    }
+}
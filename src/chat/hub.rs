@@ -0,0 +1,399 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Semaphore, broadcast, oneshot, watch};
+
+use crate::SoopHttpClient;
+use crate::chat::commands::Command;
+use crate::chat::connection::SoopChatConnection;
+use crate::chat::events::{ErrorEvent, Event, EventMeta};
+use crate::chat::filter::Lagged;
+use crate::chat::options::SoopChatOptions;
+use crate::error::{Error, Result};
+
+/// 허브가 관리하는 채널의 생명주기 상태입니다.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", content = "reason")]
+pub enum ChannelState {
+    /// 동시 연결 수 제한으로 연결 슬롯을 기다리는 중 (채널 추가 직후, 첫 접속 전)
+    Waiting,
+    /// 방송 정보를 조회하고 채팅 서버에 접속하는 중
+    Connecting,
+    /// 채팅 서버에 연결됨
+    Connected,
+    /// 연결이 끊어져 재연결을 기다리는 중
+    Reconnecting,
+    /// 방송이 꺼져 있어 방송 시작을 기다리는 중 (watch 모드)
+    Offline,
+    /// 연결이 완전히 종료됨
+    Disconnected,
+    /// 연결을 시작하지 못함 (방송 종료, 로그인 실패 등)
+    Failed(String),
+}
+
+/// 어느 채널에서 발생한 이벤트인지 함께 전달합니다.
+///
+/// 허브가 채널의 이벤트를 따라가지 못해 놓치면,
+/// 놓친 이벤트 수를 담은 `Event::Error`가 그 채널 id로 전달됩니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelEvent {
+    pub channel_id: String,
+    pub event: Event,
+}
+
+// 허브가 보관하는 채널별 핸들
+struct ChannelHandle {
+    connection: Arc<SoopChatConnection>,
+    state: watch::Receiver<ChannelState>,
+    stop_tx: Option<oneshot::Sender<()>>,
+}
+
+/// 여러 스트리머의 채팅에 동시에 연결하고, 이벤트를 하나의 스트림으로 합쳐 전달합니다.
+///
+/// 모든 채널은 하나의 `SoopHttpClient`를 공유하며,
+/// 동시에 열 수 있는 WebSocket 수를 `max_connections`로 제한합니다.
+/// 슬롯은 WebSocket 세션마다 얻고 반납하므로, 재연결 대기나 방송 대기(watch) 중인 채널은 슬롯을 차지하지 않습니다.
+pub struct SoopChatHub {
+    client: Arc<SoopHttpClient>,
+    channels: Mutex<HashMap<String, ChannelHandle>>,
+    event_tx: broadcast::Sender<ChannelEvent>,
+    semaphore: Arc<Semaphore>,
+}
+
+/// 허브 이벤트 채널의 기본 크기, 여러 채널의 이벤트가 모이므로 단일 연결보다 넉넉하게 잡습니다.
+pub const DEFAULT_HUB_CAPACITY: usize = 4096;
+
+impl SoopChatHub {
+    pub fn new(client: Arc<SoopHttpClient>, max_connections: usize) -> Self {
+        Self::with_capacity(client, max_connections, DEFAULT_HUB_CAPACITY)
+    }
+
+    /// 허브 이벤트 채널(`subscribe`)에 쌓아 둘 수 있는 이벤트 수를 지정해 허브를 만듭니다.
+    pub fn with_capacity(
+        client: Arc<SoopHttpClient>,
+        max_connections: usize,
+        capacity: usize,
+    ) -> Self {
+        let (event_tx, _) = broadcast::channel(capacity);
+
+        Self {
+            client,
+            channels: Mutex::new(HashMap::new()),
+            event_tx,
+            semaphore: Arc::new(Semaphore::new(max_connections)),
+        }
+    }
+
+    /// 채널을 추가하고 연결을 시작합니다.
+    /// 연결 슬롯이 없으면 다른 채널의 세션이 끝날 때까지 접속을 기다립니다.
+    pub fn add_channel(&self, options: SoopChatOptions) -> Result<()> {
+        let channel_id = options.streamer_id.clone();
+        let mut channels = self.channels.lock().unwrap();
+
+        if channels.contains_key(&channel_id) {
+            return Err(Error::ChannelAlreadyAdded(channel_id));
+        }
+
+        let connection = SoopChatConnection::new(Arc::clone(&self.client), options)?
+            .with_session_slots(Arc::clone(&self.semaphore));
+        let connection = Arc::new(connection);
+        let (state_tx, state_rx) = watch::channel(ChannelState::Waiting);
+        let (stop_tx, stop_rx) = oneshot::channel();
+
+        tokio::spawn(run_channel(
+            channel_id.clone(),
+            Arc::clone(&connection),
+            self.event_tx.clone(),
+            state_tx,
+            stop_rx,
+        ));
+
+        channels.insert(
+            channel_id,
+            ChannelHandle {
+                connection,
+                state: state_rx,
+                stop_tx: Some(stop_tx),
+            },
+        );
+        Ok(())
+    }
+
+    /// 채널 연결을 종료하고 허브에서 제거합니다.
+    pub fn remove_channel(&self, channel_id: &str) -> Result<()> {
+        let mut handle = self
+            .channels
+            .lock()
+            .unwrap()
+            .remove(channel_id)
+            .ok_or_else(|| Error::ChannelNotFound(channel_id.to_string()))?;
+
+        stop(&mut handle);
+        Ok(())
+    }
+
+    /// 채널의 현재 상태
+    pub fn state(&self, channel_id: &str) -> Option<ChannelState> {
+        let channels = self.channels.lock().unwrap();
+        channels
+            .get(channel_id)
+            .map(|handle| handle.state.borrow().clone())
+    }
+
+    /// 채널의 상태 변화를 지켜볼 수 있는 수신기를 얻습니다.
+    pub fn watch_state(&self, channel_id: &str) -> Option<watch::Receiver<ChannelState>> {
+        let channels = self.channels.lock().unwrap();
+        channels.get(channel_id).map(|handle| handle.state.clone())
+    }
+
+    /// 허브에 등록된 모든 채널과 상태
+    pub fn channels(&self) -> Vec<(String, ChannelState)> {
+        let channels = self.channels.lock().unwrap();
+        channels
+            .iter()
+            .map(|(id, handle)| (id.clone(), handle.state.borrow().clone()))
+            .collect()
+    }
+
+    /// 채널의 연결 핸들을 얻습니다. 채팅 전송 등 명령을 보낼 때 사용합니다.
+    pub fn connection(&self, channel_id: &str) -> Option<Arc<SoopChatConnection>> {
+        let channels = self.channels.lock().unwrap();
+        channels
+            .get(channel_id)
+            .map(|handle| Arc::clone(&handle.connection))
+    }
+
+    /// 모든 채널의 이벤트를 합친 "수신기"를 얻습니다.
+    pub fn subscribe(&self) -> broadcast::Receiver<ChannelEvent> {
+        self.event_tx.subscribe()
+    }
+}
+
+impl Drop for SoopChatHub {
+    fn drop(&mut self) {
+        if let Ok(channels) = self.channels.get_mut() {
+            channels.values_mut().for_each(stop);
+        }
+    }
+}
+
+/// 채널 작업에 종료 신호를 보냅니다.
+fn stop(handle: &mut ChannelHandle) {
+    if let Some(stop_tx) = handle.stop_tx.take() {
+        stop_tx.send(()).ok();
+    }
+}
+
+/// 채널 연결을 시작하고, 이벤트에 채널 id를 붙여 허브로 전달합니다.
+/// 연결 슬롯은 연결 루프가 WebSocket 세션마다 얻습니다.
+async fn run_channel(
+    channel_id: String,
+    connection: Arc<SoopChatConnection>,
+    event_tx: broadcast::Sender<ChannelEvent>,
+    state_tx: watch::Sender<ChannelState>,
+    mut stop_rx: oneshot::Receiver<()>,
+) {
+    state_tx.send_replace(ChannelState::Connecting);
+
+    let mut events = connection.subscribe();
+    let started = tokio::select! {
+        started = connection.start() => started,
+        // 방송 정보를 조회하는 중에 제거되면 연결하지 않고 종료합니다.
+        _ = &mut stop_rx => return,
+    };
+    if let Err(e) = started {
+        state_tx.send_replace(ChannelState::Failed(e.to_string()));
+        return;
+    }
+    // 첫 세션은 연결 슬롯을 얻은 뒤 접속합니다.
+    state_tx.send_if_modified(|state| {
+        let waiting = *state == ChannelState::Connecting;
+        if waiting {
+            *state = ChannelState::Waiting;
+        }
+        waiting
+    });
+
+    let mut stopping = false;
+    loop {
+        tokio::select! {
+            event = events.recv() => match event {
+                Ok(event) => {
+                    let is_disconnected = matches!(event, Event::Disconnected(_));
+                    if let Some(state) = lifecycle_state(&event) {
+                        state_tx.send_replace(state);
+                    }
+                    event_tx
+                        .send(ChannelEvent {
+                            channel_id: channel_id.clone(),
+                            event,
+                        })
+                        .ok();
+                    if is_disconnected {
+                        break;
+                    }
+                }
+                // 놓친 이벤트가 있었음을 진단 이벤트로 알립니다.
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    event_tx.send(lagged_event(&channel_id, skipped)).ok();
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            // 종료 신호를 받으면 Shutdown을 보내고, Disconnected 이벤트까지 전달한 뒤 종료합니다.
+            _ = &mut stop_rx, if !stopping => {
+                stopping = true;
+                connection.command(Command::Shutdown).ok();
+            }
+        }
+    }
+
+    state_tx.send_replace(ChannelState::Disconnected);
+}
+
+/// 허브가 채널의 이벤트를 놓쳤음을 알리는 이벤트
+fn lagged_event(channel_id: &str, skipped: u64) -> ChannelEvent {
+    ChannelEvent {
+        channel_id: channel_id.to_string(),
+        event: Event::Error(ErrorEvent {
+            meta: EventMeta::new(Utc::now()),
+            code: None,
+            reason: Lagged { skipped }.to_string(),
+            raw: Arc::from([]),
+        }),
+    }
+}
+
+/// 생명주기 이벤트를 채널 상태로 변환합니다.
+fn lifecycle_state(event: &Event) -> Option<ChannelState> {
+    match event {
+        Event::Connected(_) | Event::Reconnected(_) => Some(ChannelState::Connected),
+        Event::Reconnecting(_) => Some(ChannelState::Reconnecting),
+        Event::StreamOnline(_) => Some(ChannelState::Connecting),
+        Event::StreamOffline(_) => Some(ChannelState::Offline),
+        Event::Disconnected(_) => Some(ChannelState::Disconnected),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chat::reconnect::ReconnectPolicy;
+    use crate::client::{RetryPolicy, SoopApiBaseUrls};
+    use crate::constants::PLAYER_LIVE_API_PATH;
+    use crate::test_support::{MockResponse, live_detail_json, spawn_http_server, spawn_ws_server};
+    use futures_util::StreamExt;
+    use std::time::Duration;
+
+    fn unreachable_client() -> Arc<SoopHttpClient> {
+        let url = "http://127.0.0.1:9".to_string();
+        let client = SoopHttpClient::builder()
+            .base_urls(SoopApiBaseUrls {
+                live: url.clone(),
+                channel: url.clone(),
+                mobile: url.clone(),
+                login: url,
+            })
+            .retry_policy(RetryPolicy::disabled())
+            .build()
+            .unwrap();
+        Arc::new(client)
+    }
+
+    fn options(streamer_id: &str) -> SoopChatOptions {
+        SoopChatOptions {
+            streamer_id: streamer_id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_channel_lifecycle() {
+        let hub = SoopChatHub::new(unreachable_client(), 1);
+
+        hub.add_channel(options("a")).unwrap();
+        hub.add_channel(options("b")).unwrap();
+        assert!(matches!(
+            hub.add_channel(options("a")),
+            Err(Error::ChannelAlreadyAdded(_))
+        ));
+
+        // 방송 정보를 가져오지 못하면 Failed 상태가 됩니다.
+        for id in ["a", "b"] {
+            let mut state = hub.watch_state(id).unwrap();
+            let failed = state
+                .wait_for(|s| matches!(s, ChannelState::Failed(_)))
+                .await;
+            assert!(failed.is_ok());
+        }
+
+        hub.remove_channel("a").unwrap();
+        assert!(hub.state("a").is_none());
+        assert!(matches!(
+            hub.remove_channel("a"),
+            Err(Error::ChannelNotFound(_))
+        ));
+    }
+
+    async fn wait_for_state(hub: &SoopChatHub, id: &str, expected: ChannelState) {
+        let mut state = hub.watch_state(id).unwrap();
+        tokio::time::timeout(Duration::from_secs(5), state.wait_for(|s| *s == expected))
+            .await
+            .expect("채널 상태가 바뀌지 않았습니다")
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_slot_is_released_while_reconnecting() {
+        let (api, _) = spawn_http_server(|request| {
+            if request.path.starts_with(PLAYER_LIVE_API_PATH) {
+                MockResponse::ok(live_detail_json("1"))
+            } else {
+                MockResponse::status(404)
+            }
+        })
+        .await;
+        let client = SoopHttpClient::with_base_urls(SoopApiBaseUrls {
+            live: api,
+            ..Default::default()
+        });
+        let hub = SoopChatHub::new(Arc::new(client), 1);
+
+        // a는 잠시 뒤 끊기고 오래 재연결을 기다리며, b는 연결을 유지합니다.
+        let closing = spawn_ws_server(|ws_stream| async move {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            drop(ws_stream);
+        })
+        .await;
+        let open =
+            spawn_ws_server(
+                |mut ws_stream| async move { while ws_stream.next().await.is_some() {} },
+            )
+            .await;
+        hub.add_channel(SoopChatOptions {
+            reconnect: ReconnectPolicy {
+                initial_delay: Duration::from_secs(60),
+                ..ReconnectPolicy::default()
+            },
+            connection_url: Some(closing),
+            ..options("a")
+        })
+        .unwrap();
+        wait_for_state(&hub, "a", ChannelState::Connected).await;
+
+        hub.add_channel(SoopChatOptions {
+            connection_url: Some(open),
+            ..options("b")
+        })
+        .unwrap();
+        // 방송 정보는 조회했지만 a가 슬롯을 차지하고 있어 접속하지 못합니다.
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(hub.state("b"), Some(ChannelState::Waiting));
+
+        // a의 세션이 끝나면 재연결을 기다리는 동안 b가 슬롯을 얻습니다.
+        wait_for_state(&hub, "a", ChannelState::Reconnecting).await;
+        wait_for_state(&hub, "b", ChannelState::Connected).await;
+    }
+}
@@ -1,14 +1,19 @@
+use chrono::{DateTime, Utc};
 use std::sync::Arc;
-use tokio::sync::{broadcast, mpsc};
+use tokio::sync::mpsc;
 
 use crate::{
     Error, Result,
     chat::{
         Event,
-        commands::{Command, MessageType},
-        constants::message_codes::{self},
+        bus::EventBus,
+        commands::{CommandRequest, MessageType, PendingCommands},
+        constants::message_codes::{self, MessageCode},
+        decoder::DecoderRegistry,
         events::{
-            BattleMissionResultEvent, ChallengeMissionResultEvent, MissionEvent, MissionTotalEvent,
+            BJStateChangeEvent, BattleMissionResultEvent, ChallengeMissionResultEvent, ErrorEvent,
+            EventMeta, EventStamp, MissionEvent, MissionTotalEvent, ParseErrorEvent, RawEvent,
+            UnknownEvent,
         },
         formatter::ChatFormatter,
         parser::{
@@ -27,7 +32,11 @@ use crate::{
             mission::parse_mission_event,
             mute::parse_mute_event,
             notification::parse_notification_event,
-            raw::{RawMessage, parse_message},
+            raw::{RawMessage, parse_message_at},
+            room::{
+                parse_chat_room_setting_event, parse_enter_info_event, parse_manager_change_event,
+                parse_poll_event, parse_ranking_event, parse_viewer_count_event,
+            },
             slow::parse_slow_event,
             sticker::{parse_sticker_event, parse_sticker_sub_event},
             subscribe::{parse_subscribe_event, parse_subscribe_renew_event},
@@ -38,34 +47,89 @@ use crate::{
 
 pub struct MessageHandler {
     pub formatter: ChatFormatter,
-    pub event_tx: broadcast::Sender<Event>,
-    pub command_tx: mpsc::Sender<Command>,
+    // 이벤트를 구독자에게 나눠 보냅니다.
+    bus: EventBus,
+    pub command_tx: mpsc::Sender<CommandRequest>,
+    // 방송 상태 변경 메시지를 연결 루프로 넘기는 채널
+    state_change_tx: mpsc::UnboundedSender<RawMessage>,
+    // 이벤트에 채널 정보와 순번을 붙입니다.
+    stamp: EventStamp,
+    // 사용자 정의 해석기
+    decoders: DecoderRegistry,
+    // 서버의 응답을 기다리는 명령
+    pending: PendingCommands,
 }
 
 impl MessageHandler {
-    pub fn new(
+    pub(crate) fn new(
         formatter: &ChatFormatter,
-        event_tx: broadcast::Sender<Event>,
-        command_tx: mpsc::Sender<Command>,
+        bus: EventBus,
+        command_tx: mpsc::Sender<CommandRequest>,
+        state_change_tx: mpsc::UnboundedSender<RawMessage>,
+        stamp: EventStamp,
+        decoders: DecoderRegistry,
+        pending: PendingCommands,
     ) -> Self {
         Self {
             formatter: formatter.clone(),
-            event_tx,
+            bus,
             command_tx,
+            state_change_tx,
+            stamp,
+            decoders,
+            pending,
         }
     }
     /// 메시지를 처리하고 이벤트를 전송합니다.
     pub fn handle(&self, raw: Vec<u8>) -> Result<Option<Vec<u8>>> {
-        // Arc로 감싸서 클로닝 오버헤드를 줄입니다.
-        let raw_arc: Arc<[u8]> = raw.clone().into();
-        // Raw 메시지 처리
-        self.broadcast(Event::Raw(raw_arc))?;
+        self.handle_at(raw, Utc::now())
+    }
+
+    /// 수신 시간을 지정하여 메시지를 처리합니다. 녹화된 패킷을 재생할 때 사용합니다.
+    pub(crate) fn handle_at(
+        &self,
+        raw: Vec<u8>,
+        received_time: DateTime<Utc>,
+    ) -> Result<Option<Vec<u8>>> {
+        // Raw 메시지는 구독자가 있을 때만 복사해서 방송합니다.
+        if self.bus.wants_raw() {
+            let _ = self.broadcast(Event::Raw(RawEvent {
+                meta: EventMeta::new(received_time),
+                data: Arc::from(raw.as_slice()),
+            }));
+        }
         // 메시지 파싱
-        let ret = match parse_message(&raw) {
-            Ok(message) => self.handle_message(message),
-            Err(_) => {
-                // 파싱 오류 처리
-                // self.broadcast(Event::Error(e))?;
+        let ret = match parse_message_at(&raw, received_time) {
+            // 기다리던 명령의 거부 응답은 이벤트로 만들지 않습니다.
+            Ok(message) if self.pending.resolve(&message) => None,
+            Ok(message) => {
+                let code = message.code;
+                match self.handle_message(message) {
+                    Ok(ret) => ret,
+                    // 본문 필드가 부족하거나 잘못된 경우, 원본 데이터와 함께 알립니다.
+                    Err(Error::ParseError {
+                        code,
+                        field,
+                        body_len,
+                    }) => {
+                        let _ = self.broadcast(Event::ParseError(ParseErrorEvent {
+                            meta: EventMeta::new(received_time),
+                            code,
+                            field,
+                            body_len,
+                            raw: raw.into(),
+                        }));
+                        None
+                    }
+                    Err(e) => {
+                        self.report_error(received_time, Some(code), e.to_string(), raw.into());
+                        None
+                    }
+                }
+            }
+            Err(e) => {
+                // 헤더조차 해석하지 못한 경우
+                self.report_error(received_time, None, e.to_string(), raw.into());
                 None
             }
         };
@@ -73,14 +137,35 @@ impl MessageHandler {
         Ok(ret)
     }
 
+    /// 처리하지 못한 메시지를 Error 이벤트로 알립니다.
+    fn report_error(
+        &self,
+        received_time: DateTime<Utc>,
+        code: Option<MessageCode>,
+        reason: String,
+        raw: Arc<[u8]>,
+    ) {
+        let _ = self.broadcast(Event::Error(ErrorEvent {
+            meta: EventMeta::new(received_time),
+            code,
+            reason,
+            raw,
+        }));
+    }
+
     fn broadcast(&self, event: Event) -> Result<()> {
-        self.event_tx
-            .send(event)
-            .map_err(|e| Error::InternalChannel(e.to_string()))?;
-        Ok(())
+        self.bus.send(self.stamp.stamp(event))
     }
 
-    fn handle_message(&self, message: RawMessage) -> Option<Vec<u8>> {
+    fn handle_message(&self, message: RawMessage) -> Result<Option<Vec<u8>>> {
+        // 사용자 정의 해석기가 등록된 코드는 기본 해석기 대신 사용합니다.
+        if let Some(decoder) = self.decoders.get(message.code) {
+            if let Some(event) = decoder.decode(message) {
+                let _ = self.broadcast(event);
+            }
+            return Ok(None);
+        }
+
         // 메시지 처리 로직을 여기에 구현합니다.
         // 예를 들어, raw 메시지를 파싱하고 필요한 이벤트를 생성할 수 있습니다.
         let res = match message.code {
@@ -98,6 +183,15 @@ impl MessageHandler {
             message_codes::KICK_CANCEL => self.handle_kick_cancel(message),
             message_codes::SUBSCRIBE => self.handle_subscribe(message),
             message_codes::SUBSCRIBE_RENEW => self.handle_subscribe_renew(message),
+            // 채팅방 정보
+            message_codes::ENTER_INFO => self.handle_enter_info(message),
+            message_codes::MANAGER_CHANGE => self.handle_manager_change(message),
+            message_codes::VIEWER_COUNT => self.handle_viewer_count(message),
+            message_codes::TOP_FAN | message_codes::TOP_FAN_SUB => self.handle_ranking(message),
+            message_codes::POLL => self.handle_poll(message),
+            message_codes::ICE_MODE | message_codes::CHAT_BLOCK_MODE => {
+                self.handle_chat_room_setting(message)
+            }
             // 스티커
             message_codes::STICKER | message_codes::SUB_STICKER => self.handle_sticker(message),
             // 선물
@@ -117,8 +211,13 @@ impl MessageHandler {
 
             _ => {
                 // 다른 메시지 코드 처리
-                let _ = self.broadcast(Event::Unknown(message.code));
-                None
+                let _ = self.broadcast(Event::Unknown(UnknownEvent {
+                    meta: EventMeta::new(message.received_time),
+                    code: message.code,
+                    name: message_codes::unparsed_name(message.code).map(str::to_string),
+                    body: message.body,
+                }));
+                Ok(None)
             }
         };
 
@@ -126,7 +225,7 @@ impl MessageHandler {
         res
     }
 
-    fn handle_donation(&self, message: RawMessage) -> Option<Vec<u8>> {
+    fn handle_donation(&self, message: RawMessage) -> Result<Option<Vec<u8>>> {
         let e = match message.code {
             message_codes::DONATION => parse_balloon_event(message),
             message_codes::SUB_DONATION => parse_balloon_sub_event(message),
@@ -135,144 +234,347 @@ impl MessageHandler {
             message_codes::ADBALLOON_DONATION => parse_ad_balloon_event(message),
             message_codes::AD_STATION_DONATION => parse_station_ad_balloon_event(message),
             message_codes::VIDEO_DONATION => parse_video_balloon_event(message),
-            _ => return None,
+            _ => return Ok(None),
         };
-        let _ = self.broadcast(Event::Donation(e));
-        None
+        let _ = self.broadcast(Event::Donation(e?));
+        Ok(None)
     }
 
-    fn handle_sticker(&self, message: RawMessage) -> Option<Vec<u8>> {
+    fn handle_sticker(&self, message: RawMessage) -> Result<Option<Vec<u8>>> {
         let e = match message.code {
             message_codes::STICKER => parse_sticker_event(message),
             message_codes::SUB_STICKER => parse_sticker_sub_event(message),
-            _ => return None,
+            _ => return Ok(None),
         };
-        let _ = self.broadcast(Event::Sticker(e));
-        None
+        let _ = self.broadcast(Event::Sticker(e?));
+        Ok(None)
     }
 
-    fn handle_gift(&self, message: RawMessage) -> Option<Vec<u8>> {
+    fn handle_gift(&self, message: RawMessage) -> Result<Option<Vec<u8>>> {
         let e = match message.code {
             message_codes::OGQ_GIFTED => parse_ogq_gift_event(message),
             message_codes::SUBSCRIPTION_GIFTED => parse_subscribe_gift_event(message),
             message_codes::QUICKVIEW_GIFTED => parse_quickview_gift_event(message),
-            _ => return None,
+            _ => return Ok(None),
         };
-        let _ = self.broadcast(Event::Gift(e));
-        None
+        let _ = self.broadcast(Event::Gift(e?));
+        Ok(None)
     }
 
-    fn handle_mission(&self, message: RawMessage) -> Option<Vec<u8>> {
-        if let Some((name, val)) = parse_mission_event(message).ok() {
-            match name {
-                MissionParser::Mission => {
-                    if let Ok(be) = val.downcast::<MissionEvent>() {
-                        let e: MissionEvent = *be;
-                        let _ = self.broadcast(Event::MissionDonation(e));
-                    }
+    fn handle_mission(&self, message: RawMessage) -> Result<Option<Vec<u8>>> {
+        let (name, val) = parse_mission_event(message)?;
+
+        match name {
+            MissionParser::Mission => {
+                if let Ok(be) = val.downcast::<MissionEvent>() {
+                    let e: MissionEvent = *be;
+                    let _ = self.broadcast(Event::MissionDonation(e));
                 }
-                MissionParser::MissionTotal => {
-                    if let Ok(be) = val.downcast::<MissionTotalEvent>() {
-                        let e: MissionTotalEvent = *be;
-                        let _ = self.broadcast(Event::MissionTotal(e));
-                    }
+            }
+            MissionParser::MissionTotal => {
+                if let Ok(be) = val.downcast::<MissionTotalEvent>() {
+                    let e: MissionTotalEvent = *be;
+                    let _ = self.broadcast(Event::MissionTotal(e));
                 }
-                MissionParser::BattleNotice => {
-                    if let Ok(be) = val.downcast::<BattleMissionResultEvent>() {
-                        let e: BattleMissionResultEvent = *be;
-                        let _ = self.broadcast(Event::BattleMissionResult(e));
-                    }
+            }
+            MissionParser::BattleNotice => {
+                if let Ok(be) = val.downcast::<BattleMissionResultEvent>() {
+                    let e: BattleMissionResultEvent = *be;
+                    let _ = self.broadcast(Event::BattleMissionResult(e));
                 }
-                MissionParser::ChallengeNotice => {
-                    if let Ok(be) = val.downcast::<ChallengeMissionResultEvent>() {
-                        let e: ChallengeMissionResultEvent = *be;
-                        let _ = self.broadcast(Event::ChallengeMissionResult(e));
-                    }
+            }
+            MissionParser::ChallengeNotice => {
+                if let Ok(be) = val.downcast::<ChallengeMissionResultEvent>() {
+                    let e: ChallengeMissionResultEvent = *be;
+                    let _ = self.broadcast(Event::ChallengeMissionResult(e));
                 }
             }
         }
 
-        None
+        Ok(None)
+    }
+
+    fn handle_subscribe_renew(&self, message: RawMessage) -> Result<Option<Vec<u8>>> {
+        let _ = self.broadcast(Event::Subscribe(parse_subscribe_renew_event(message)?));
+        Ok(None)
+    }
+
+    fn handle_subscribe(&self, message: RawMessage) -> Result<Option<Vec<u8>>> {
+        let _ = self.broadcast(Event::Subscribe(parse_subscribe_event(message)?));
+        Ok(None)
+    }
+
+    fn handle_slow(&self, message: RawMessage) -> Result<Option<Vec<u8>>> {
+        let _ = self.broadcast(Event::Slow(parse_slow_event(message)?));
+        Ok(None)
+    }
+
+    /// 방송 상태 변경은 방송 정보를 다시 조회해야 하므로 연결 루프로 넘깁니다.
+    fn handle_bj_state_change(&self, message: RawMessage) -> Result<Option<Vec<u8>>> {
+        if let Err(mpsc::error::SendError(message)) = self.state_change_tx.send(message) {
+            // 연결 루프가 받을 수 없으면 비교 없이 바로 알립니다.
+            let _ = self.broadcast(Event::BJStateChange(BJStateChangeEvent {
+                meta: EventMeta::new(message.received_time),
+                changes: Vec::new(),
+                before: None,
+                after: None,
+                body: message.body,
+            }));
+        }
+        Ok(None)
+    }
+
+    fn handle_enter_info(&self, message: RawMessage) -> Result<Option<Vec<u8>>> {
+        let _ = self.broadcast(Event::EnterInfo(parse_enter_info_event(message)?));
+        Ok(None)
+    }
+
+    fn handle_manager_change(&self, message: RawMessage) -> Result<Option<Vec<u8>>> {
+        let _ = self.broadcast(Event::ManagerChange(parse_manager_change_event(message)?));
+        Ok(None)
     }
 
-    fn handle_subscribe_renew(&self, message: RawMessage) -> Option<Vec<u8>> {
-        let _ = self.broadcast(Event::Subscribe(parse_subscribe_renew_event(message)));
-        None
+    fn handle_viewer_count(&self, message: RawMessage) -> Result<Option<Vec<u8>>> {
+        let _ = self.broadcast(Event::ViewerCount(parse_viewer_count_event(message)?));
+        Ok(None)
     }
 
-    fn handle_subscribe(&self, message: RawMessage) -> Option<Vec<u8>> {
-        let _ = self.broadcast(Event::Subscribe(parse_subscribe_event(message)));
-        None
+    fn handle_ranking(&self, message: RawMessage) -> Result<Option<Vec<u8>>> {
+        let _ = self.broadcast(Event::Ranking(parse_ranking_event(message)?));
+        Ok(None)
     }
 
-    fn handle_slow(&self, message: RawMessage) -> Option<Vec<u8>> {
-        let _ = self.broadcast(Event::Slow(parse_slow_event(message)));
-        None
+    fn handle_poll(&self, message: RawMessage) -> Result<Option<Vec<u8>>> {
+        let _ = self.broadcast(Event::Poll(parse_poll_event(message)?));
+        Ok(None)
     }
 
-    fn handle_bj_state_change(&self, _: RawMessage) -> Option<Vec<u8>> {
-        let _ = self.broadcast(Event::BJStateChange);
-        None
+    fn handle_chat_room_setting(&self, message: RawMessage) -> Result<Option<Vec<u8>>> {
+        let _ = self.broadcast(Event::ChatRoomSetting(parse_chat_room_setting_event(
+            message,
+        )?));
+        Ok(None)
     }
 
-    fn handle_emoticon_message(&self, message: RawMessage) -> Option<Vec<u8>> {
-        let _ = self.broadcast(Event::Chat(parse_emoticon_event(message)));
-        None
+    fn handle_emoticon_message(&self, message: RawMessage) -> Result<Option<Vec<u8>>> {
+        let _ = self.broadcast(Event::Chat(parse_emoticon_event(message)?));
+        Ok(None)
     }
 
-    fn handle_notification(&self, message: RawMessage) -> Option<Vec<u8>> {
-        let _ = self.broadcast(Event::Notification(parse_notification_event(message)));
-        None
+    fn handle_notification(&self, message: RawMessage) -> Result<Option<Vec<u8>>> {
+        let _ = self.broadcast(Event::Notification(parse_notification_event(message)?));
+        Ok(None)
     }
 
-    fn handle_manager_message(&self, message: RawMessage) -> Option<Vec<u8>> {
-        let _ = self.broadcast(Event::Chat(parse_manager_chat_event(message)));
-        None
+    fn handle_manager_message(&self, message: RawMessage) -> Result<Option<Vec<u8>>> {
+        let _ = self.broadcast(Event::Chat(parse_manager_chat_event(message)?));
+        Ok(None)
     }
 
-    fn handle_kick_cancel(&self, message: RawMessage) -> Option<Vec<u8>> {
-        if let Some(e) = parse_kick_cancel_event(message) {
+    fn handle_kick_cancel(&self, message: RawMessage) -> Result<Option<Vec<u8>>> {
+        if let Some(e) = parse_kick_cancel_event(message)? {
             let _ = self.broadcast(Event::KickCancel(e));
         }
-        None
+        Ok(None)
     }
 
-    fn handle_join(&self, message: RawMessage) -> Option<Vec<u8>> {
-        if let Some(e) = parse_join_event(message) {
+    fn handle_join(&self, message: RawMessage) -> Result<Option<Vec<u8>>> {
+        if let Some(e) = parse_join_event(message)? {
             let _ = self.broadcast(Event::Join(e));
         }
-        None
+        Ok(None)
     }
 
-    fn handle_mute(&self, message: RawMessage) -> Option<Vec<u8>> {
-        let _ = self.broadcast(Event::Mute(parse_mute_event(message)));
-        None
+    fn handle_mute(&self, message: RawMessage) -> Result<Option<Vec<u8>>> {
+        let _ = self.broadcast(Event::Mute(parse_mute_event(message)?));
+        Ok(None)
     }
 
-    fn handle_freeze(&self, message: RawMessage) -> Option<Vec<u8>> {
-        let _ = self.broadcast(Event::Freeze(parse_freeze_event(message)));
-        None
+    fn handle_freeze(&self, message: RawMessage) -> Result<Option<Vec<u8>>> {
+        let _ = self.broadcast(Event::Freeze(parse_freeze_event(message)?));
+        Ok(None)
     }
 
-    fn handle_chat(&self, message: RawMessage) -> Option<Vec<u8>> {
-        let _ = self.broadcast(Event::Chat(parse_chat_event(message)));
-        None
+    fn handle_chat(&self, message: RawMessage) -> Result<Option<Vec<u8>>> {
+        let _ = self.broadcast(Event::Chat(parse_chat_event(message)?));
+        Ok(None)
     }
 
-    fn handle_exit(&self, message: RawMessage) -> Option<Vec<u8>> {
-        if let Some((is_kick, e)) = parse_exit_event(message) {
+    fn handle_exit(&self, message: RawMessage) -> Result<Option<Vec<u8>>> {
+        if let Some((is_kick, e)) = parse_exit_event(message)? {
             if is_kick {
                 let _ = self.broadcast(Event::Kick(e));
             } else {
                 let _ = self.broadcast(Event::Exit(e));
             };
         }
-        None
+        Ok(None)
     }
 
     // CONNECT 메시지 처리 -> JOIN 메시지 전송
-    fn handle_connect(&self, _: RawMessage) -> Option<Vec<u8>> {
+    fn handle_connect(&self, _: RawMessage) -> Result<Option<Vec<u8>>> {
         let ret = self.formatter.format_message(MessageType::JOIN);
-        Some(ret)
+        Ok(Some(ret))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        chat::{commands::Command, formatter::bundle, types::SuperUser},
+        models::LiveDetail,
+    };
+    use std::time::Duration;
+    use tokio::sync::oneshot;
+
+    fn handler() -> (MessageHandler, EventBus) {
+        let (handler, bus, _) = handler_with_pending();
+        (handler, bus)
+    }
+
+    fn handler_with_pending() -> (MessageHandler, EventBus, PendingCommands) {
+        let live_detail = LiveDetail {
+            is_live: true,
+            ch_domain: "chat.sooplive.co.kr".to_string(),
+            ch_pt: 8000,
+            ch_no: "1".to_string(),
+            streamer_nick: "".to_string(),
+            title: "".to_string(),
+            categories: vec![],
+            is_password: false,
+            is_adult: false,
+        };
+        let formatter = ChatFormatter::new(live_detail, "".to_string(), None);
+        let bus = EventBus::new(&Default::default());
+        let (command_tx, _) = mpsc::channel(1);
+        let pending = PendingCommands::default();
+
+        (
+            MessageHandler::new(
+                &formatter,
+                bus.clone(),
+                command_tx,
+                mpsc::unbounded_channel().0,
+                EventStamp::new("streamer", "1"),
+                DecoderRegistry::default(),
+                pending.clone(),
+            ),
+            bus,
+            pending,
+        )
+    }
+
+    /// 채팅 패킷, 필드 순서는 chat_message_fields를 따릅니다.
+    fn chat_packet(user_id: &str) -> Vec<u8> {
+        let body = format!(
+            "\x0c안녕\x0c{user_id}\x0c0\x0c0\x0c0\x0c닉네임\x0c0|0\x0c0\x0c0\x0c0\x0c0\x0c"
+        );
+        bundle(MessageType::Chat, body.as_bytes())
+    }
+
+    /// 헤더의 응답 코드를 바꿉니다.
+    fn with_ret_code(mut packet: Vec<u8>, ret_code: &[u8; 2]) -> Vec<u8> {
+        packet[12..14].copy_from_slice(ret_code);
+        packet
+    }
+
+    #[test]
+    fn test_command_is_acknowledged_by_own_echo() {
+        let (handler, bus, pending) = handler_with_pending();
+        let mut events = bus.subscribe();
+        let (responder, mut response) = oneshot::channel();
+        let ack = Command::SendChat("안녕".to_string()).ack("me").unwrap();
+        pending.push(ack, responder, Duration::from_secs(10));
+
+        // 다른 사용자의 채팅은 응답이 아닙니다.
+        handler.handle(chat_packet("other")).unwrap();
+        assert!(response.try_recv().is_err());
+
+        handler.handle(chat_packet("me(2)")).unwrap();
+        assert!(matches!(response.try_recv(), Ok(Ok(()))));
+        // 응답 패킷도 평소처럼 이벤트로 방송됩니다.
+        assert!(matches!(events.try_recv(), Ok(Event::Chat(e)) if e.user.id == "other"));
+        assert!(matches!(events.try_recv(), Ok(Event::Chat(e)) if e.user.id == "me"));
+    }
+
+    #[test]
+    fn test_command_rejection_and_timeout() {
+        let (handler, bus, pending) = handler_with_pending();
+        let mut events = bus.subscribe();
+        let mute = Command::Mute {
+            user_id: "target".to_string(),
+            seconds: 30,
+            superuser_type: SuperUser::Manager,
+        };
+        let (responder, mut response) = oneshot::channel();
+        pending.push(mute.ack("me").unwrap(), responder, Duration::from_secs(10));
+
+        let rejection = bundle(MessageType::Mute, "\x0c권한이 없습니다\x0c".as_bytes());
+        handler.handle(with_ret_code(rejection, b"03")).unwrap();
+        match response.try_recv() {
+            Ok(Err(Error::CommandRejected { code, reason })) => {
+                assert_eq!(code, 3);
+                assert_eq!(reason, "권한이 없습니다");
+            }
+            e => panic!("unexpected response: {e:?}"),
+        }
+        // 거부 응답은 이벤트로 만들지 않습니다.
+        assert!(events.try_recv().is_err());
+
+        // 응답이 오지 않으면 기한이 지난 뒤 CommandTimeout으로 끝납니다.
+        let (responder, mut response) = oneshot::channel();
+        pending.push(mute.ack("me").unwrap(), responder, Duration::ZERO);
+        assert!(pending.next_deadline().is_some());
+        pending.expire(tokio::time::Instant::now());
+        assert!(matches!(
+            response.try_recv(),
+            Ok(Err(Error::CommandTimeout))
+        ));
+        assert!(pending.next_deadline().is_none());
+    }
+
+    #[test]
+    fn test_short_body_is_reported_as_diagnostic() {
+        let (handler, bus) = handler();
+        let mut events = bus.subscribe();
+        let mut raw = bus.subscribe_raw();
+        let mut diagnostics = bus.subscribe_diagnostics();
+
+        // 필드가 모자란 CHAT 메시지
+        let packet = bundle(MessageType::Chat, b"\x0chello\x0c");
+        assert!(handler.handle(packet).unwrap().is_none());
+
+        assert!(matches!(raw.try_recv(), Ok(Event::Raw(e)) if e.meta.seq == 1));
+        match events.try_recv() {
+            Ok(Event::ParseError(e)) => {
+                assert_eq!(e.code, message_codes::CHAT);
+                assert_eq!(e.meta.streamer_id, "streamer");
+                assert_eq!(e.meta.chat_no, "1");
+                assert_eq!(e.meta.seq, 2);
+            }
+            e => panic!("unexpected event: {e:?}"),
+        }
+        assert!(matches!(diagnostics.try_recv(), Ok(Event::ParseError(_))));
+    }
+
+    #[test]
+    fn test_invalid_mission_json_is_reported_as_error() {
+        let (handler, bus) = handler();
+        let mut diagnostics = bus.subscribe_diagnostics();
+
+        let mut packet = bundle(MessageType::Unknown, b"\x0c{not json\x0c");
+        // MISSION_DONATION 코드로 헤더를 바꿉니다.
+        packet[2..6].copy_from_slice(b"0121");
+        handler.handle(packet).unwrap();
+
+        match diagnostics.try_recv() {
+            Ok(Event::Error(e)) => {
+                assert_eq!(e.code, Some(message_codes::MISSION_DONATION));
+                // Raw 구독자가 없으면 Raw 이벤트를 만들지 않습니다.
+                assert_eq!(e.meta.seq, 1);
+            }
+            e => panic!("unexpected event: {e:?}"),
+        }
     }
 }
@@ -1,18 +1,35 @@
+pub mod capture;
+mod bus;
 pub mod commands;
 pub mod connection;
 pub mod constants;
+pub mod decoder;
 pub mod events;
+pub mod filter;
 mod formatter;
+pub mod hub;
 pub mod message;
 pub mod options;
-mod parser;
+pub(crate) mod parser;
+pub mod reconnect;
+pub mod replay;
 pub mod types;
 mod verification;
 
+pub use capture::{CaptureReader, CaptureWriter, CapturedFrame, spawn_recorder};
 pub use connection::SoopChatConnection;
+pub use decoder::PacketDecoder;
+pub use filter::{EventFilter, EventStats, EventStream, Lagged};
+pub use parser::raw::{RawMessage, parse_message};
+pub use hub::{ChannelEvent, ChannelState, SoopChatHub};
 pub use events::{
-    BattleMissionResultEvent, ChallengeMissionResultEvent, ChatEvent, ConnectedEvent,
-    DonationEvent, Event, EventMeta, FreezeEvent, MissionEvent, MissionTotalEvent, MuteEvent,
-    NotificationEvent, SimplifiedUserEvent, SlowEvent, SubscribeEvent, UserEvent,
+    BattleMissionResultEvent, ChallengeMissionResultEvent, BJStateChangeEvent, ChatEvent, ChatRoomSettingEvent, ConnectedEvent, EventKind, CustomEvent, EnterInfoEvent, ManagerChangeEvent, PollEvent, RankingEvent, ViewerCountEvent, DisconnectedEvent, RawEvent, UnknownEvent,
+    DonationEvent, ErrorEvent, Event, EventMeta, FreezeEvent, MissionEvent, MissionTotalEvent, MuteEvent,
+    NotificationEvent, ParseErrorEvent, ReconnectingEvent, VersionedEvent, EVENT_SCHEMA_VERSION, SimplifiedUserEvent, SlowEvent, StreamOfflineEvent, StreamOnlineEvent, SubscribeEvent,
+    UserEvent,
 };
-pub use options::SoopChatOptions;
+pub use options::{
+    SoopChatOptions, SoopEventOptions, SoopLoginOptions, SoopTlsOptions, SoopWatchOptions, TlsVerification,
+};
+pub use reconnect::ReconnectPolicy;
+pub use replay::{ReplayConnection, ReplaySpeed};
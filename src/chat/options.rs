@@ -1,9 +1,116 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use rustls::{RootCertStore, crypto::CryptoProvider, pki_types::CertificateDer};
+
+use crate::chat::reconnect::ReconnectPolicy;
+
 // --- 설정 옵션 구조체 ---
 #[derive(Clone, Debug)]
 pub struct SoopChatOptions {
     pub streamer_id: String,
     pub password: String,
+    // 연결이 끊어졌을 때의 재연결 정책
+    pub reconnect: ReconnectPolicy,
     // 로그인 정보는 선택 사항이므로 Option으로 감쌉니다.
-    // pub login: Option<SoopLoginOptions>,
-    // pub base_urls: Option<SoopAPIBaseUrls>,
+    pub login: Option<SoopLoginOptions>,
+    // 방송 대기(watch) 모드, 설정하면 방송이 꺼져 있어도 시작할 수 있습니다.
+    pub watch: Option<SoopWatchOptions>,
+    // 채팅 서버 연결에 사용할 TLS 설정
+    pub tls: SoopTlsOptions,
+    // 채팅 서버 WebSocket 주소를 직접 지정합니다. (mock 서버, relay 등)
+    // None이면 방송 정보의 채팅 서버 주소를 사용합니다.
+    pub connection_url: Option<String>,
+    // 이벤트 채널 크기
+    pub events: SoopEventOptions,
+    // `execute`로 보낸 명령에 대한 서버 응답을 기다리는 시간 (기본 10초)
+    pub command_timeout: Duration,
+}
+
+impl Default for SoopChatOptions {
+    fn default() -> Self {
+        Self {
+            streamer_id: String::new(),
+            password: String::new(),
+            reconnect: ReconnectPolicy::default(),
+            login: None,
+            watch: None,
+            tls: SoopTlsOptions::default(),
+            connection_url: None,
+            events: SoopEventOptions::default(),
+            command_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+// --- 로그인 옵션 ---
+// 채팅 전송 등 인증이 필요한 기능에 사용됩니다.
+#[derive(Clone, Debug)]
+pub struct SoopLoginOptions {
+    pub user_id: String,
+    pub password: String,
+}
+
+// --- 방송 대기(watch) 옵션 ---
+// 방송이 시작되면 자동으로 채팅에 접속하고, 방송이 끝나면 다시 방송을 기다립니다.
+// 방송 상태가 바뀔 때마다 StreamOnline / StreamOffline 이벤트가 발생합니다.
+#[derive(Clone, Debug)]
+pub struct SoopWatchOptions {
+    // 방송 상태를 확인하는 주기
+    pub poll_interval: Duration,
+}
+
+impl Default for SoopWatchOptions {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(30),
+        }
+    }
+}
+
+// --- 이벤트 채널 옵션 ---
+// 구독자가 처리하는 속도보다 이벤트가 빨리 쌓이면, 채널 크기를 넘는 이벤트는 버려집니다.
+// 놓친 이벤트는 수신기에 Lagged로 알려지며, 버려지면 안 되는 구독은
+// `SoopChatConnection::subscribe_lossless`를 사용합니다.
+#[derive(Clone, Debug)]
+pub struct SoopEventOptions {
+    // 이벤트 채널(subscribe, subscribe_raw)과 필터 구독마다 쌓아 둘 수 있는 이벤트 수
+    pub capacity: usize,
+    // 진단 채널(subscribe_diagnostics)에 쌓아 둘 수 있는 이벤트 수
+    pub diagnostic_capacity: usize,
+}
+
+impl Default for SoopEventOptions {
+    fn default() -> Self {
+        Self {
+            capacity: 1024,
+            diagnostic_capacity: 256,
+        }
+    }
+}
+
+// --- TLS 옵션 ---
+// 채팅 서버(WebSocket) 연결 시 인증서 검증 방식과 암호화 구현체를 지정합니다.
+#[derive(Clone, Debug, Default)]
+pub struct SoopTlsOptions {
+    pub verification: TlsVerification,
+    // None이면 프로세스 기본 CryptoProvider를, 그마저 없으면 ring을 사용합니다.
+    // 전역 기본값은 변경하지 않습니다.
+    pub crypto_provider: Option<Arc<CryptoProvider>>,
+}
+
+/// 서버 인증서 검증 방식
+#[derive(Clone, Debug, Default)]
+pub enum TlsVerification {
+    /// webpki-roots에 포함된 공인 루트 인증서로 검증합니다.
+    #[default]
+    WebPki,
+    /// 직접 구성한 루트 인증서 저장소로 검증합니다.
+    CustomRoots(Arc<RootCertStore>),
+    /// 서버가 제시한 인증서가 지정한 인증서와 정확히 일치할 때만 허용합니다.
+    /// 체인, 호스트 이름, 유효기간 검증을 대신합니다.
+    Pinned(CertificateDer<'static>),
+    /// 인증서를 검증하지 않습니다. 중간자 공격에 노출되므로 디버깅 용도로만 사용하세요.
+    #[cfg(feature = "dangerous-insecure-tls")]
+    Insecure,
 }
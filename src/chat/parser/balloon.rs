@@ -1,115 +1,141 @@
-use crate::chat::{DonationEvent, events::EventMeta, parser::{raw::RawMessage, util::{normalize_user_id, parse_u32_or_default}}, types::DonationType};
+use crate::{
+    Result,
+    chat::{
+        DonationEvent,
+        events::EventMeta,
+        parser::{raw::RawMessage, util::normalize_user_id},
+        types::DonationType,
+    },
+};
 
-pub fn parse_balloon_event(raw: RawMessage) -> DonationEvent {
-    let body = raw.body;
-
-    DonationEvent {
-        meta: EventMeta {
-            received_time: raw.received_time,
-        },
+pub fn parse_balloon_event(raw: RawMessage) -> Result<DonationEvent> {
+    Ok(DonationEvent {
+        meta: EventMeta::new(raw.received_time),
         donation_type: DonationType::Balloon,
-        from: normalize_user_id(&body[1]),
-        from_label: body[2].clone(),
-        amount: parse_u32_or_default(&body[3]),
-        fan_club_ordinal: parse_u32_or_default(&body[4]),
-        become_top_fan: "1" == body[8],
-    }
+        from: normalize_user_id(raw.field(1, "from")?),
+        from_label: raw.field(2, "from_label")?.to_string(),
+        amount: raw.number(3, "amount", 0)?,
+        fan_club_ordinal: raw.number(4, "fan_club_ordinal", 0)?,
+        become_top_fan: "1" == raw.field(8, "become_top_fan")?,
+    })
 }
 
-pub fn parse_balloon_sub_event(raw: RawMessage) -> DonationEvent {
-    let body = raw.body;
-
-    DonationEvent {
-        meta: EventMeta {
-            received_time: raw.received_time,
-        },
+pub fn parse_balloon_sub_event(raw: RawMessage) -> Result<DonationEvent> {
+    Ok(DonationEvent {
+        meta: EventMeta::new(raw.received_time),
         donation_type: DonationType::Balloon,
-        from: normalize_user_id(&body[3]),
-        from_label: body[4].clone(),
-        amount: parse_u32_or_default(&body[5]),
-        fan_club_ordinal: parse_u32_or_default(&body[6]),
-        become_top_fan: "1" == body[9],
-    }
+        from: normalize_user_id(raw.field(3, "from")?),
+        from_label: raw.field(4, "from_label")?.to_string(),
+        amount: raw.number(5, "amount", 0)?,
+        fan_club_ordinal: raw.number(6, "fan_club_ordinal", 0)?,
+        become_top_fan: "1" == raw.field(9, "become_top_fan")?,
+    })
 }
 
-pub fn parse_vod_balloon_event(raw: RawMessage) -> DonationEvent {
-    let body = raw.body;
-
-    DonationEvent {
-        meta: EventMeta {
-            received_time: raw.received_time,
-        },
+pub fn parse_vod_balloon_event(raw: RawMessage) -> Result<DonationEvent> {
+    Ok(DonationEvent {
+        meta: EventMeta::new(raw.received_time),
         donation_type: DonationType::Balloon,
-        from: normalize_user_id(&body[1]),
-        from_label: body[2].clone(),
-        amount: parse_u32_or_default(&body[3]),
+        from: normalize_user_id(raw.field(1, "from")?),
+        from_label: raw.field(2, "from_label")?.to_string(),
+        amount: raw.number(3, "amount", 0)?,
         fan_club_ordinal: 0,
         become_top_fan: false,
-    }
+    })
 }
 
-/// --- ad balloon
+// --- ad balloon
 
-pub fn parse_vod_ad_balloon_event(raw: RawMessage) -> DonationEvent {
-    let body = raw.body;
-
-    DonationEvent {
-        meta: EventMeta {
-            received_time: raw.received_time,
-        },
+pub fn parse_vod_ad_balloon_event(raw: RawMessage) -> Result<DonationEvent> {
+    Ok(DonationEvent {
+        meta: EventMeta::new(raw.received_time),
         donation_type: DonationType::ADBalloon,
-        from: normalize_user_id(&body[1]),
-        from_label: body[2].clone(),
-        amount: parse_u32_or_default(&body[3]),
+        from: normalize_user_id(raw.field(1, "from")?),
+        from_label: raw.field(2, "from_label")?.to_string(),
+        amount: raw.number(3, "amount", 0)?,
         fan_club_ordinal: 0,
         become_top_fan: false,
-    }
+    })
 }
 
-pub fn parse_ad_balloon_event(raw: RawMessage) -> DonationEvent {
-    let body = raw.body;
-
-    DonationEvent {
-        meta: EventMeta {
-            received_time: raw.received_time,
-        },
+pub fn parse_ad_balloon_event(raw: RawMessage) -> Result<DonationEvent> {
+    Ok(DonationEvent {
+        meta: EventMeta::new(raw.received_time),
         donation_type: DonationType::ADBalloon,
-        from: normalize_user_id(&body[2]),
-        from_label: body[3].clone(),
-        amount: parse_u32_or_default(&body[9]),
-        fan_club_ordinal: parse_u32_or_default(&body[10]),
-        become_top_fan: body[12] == "1",
-    }
+        from: normalize_user_id(raw.field(2, "from")?),
+        from_label: raw.field(3, "from_label")?.to_string(),
+        amount: raw.number(9, "amount", 0)?,
+        fan_club_ordinal: raw.number(10, "fan_club_ordinal", 0)?,
+        become_top_fan: raw.field(12, "become_top_fan")? == "1",
+    })
 }
 
-pub fn parse_station_ad_balloon_event(raw: RawMessage) -> DonationEvent {
-    let body = raw.body;
-
-    DonationEvent {
-        meta: EventMeta {
-            received_time: raw.received_time,
-        },
+pub fn parse_station_ad_balloon_event(raw: RawMessage) -> Result<DonationEvent> {
+    Ok(DonationEvent {
+        meta: EventMeta::new(raw.received_time),
         donation_type: DonationType::ADBalloon,
-        from: normalize_user_id(&body[1]),
-        from_label: body[2].clone(),
-        amount: parse_u32_or_default(&body[3]),
+        from: normalize_user_id(raw.field(1, "from")?),
+        from_label: raw.field(2, "from_label")?.to_string(),
+        amount: raw.number(3, "amount", 0)?,
         fan_club_ordinal: 0,
         become_top_fan: false,
-    }
+    })
 }
 
-pub fn parse_video_balloon_event(raw: RawMessage) -> DonationEvent {
-    let body = raw.body;
-
-    DonationEvent {
-        meta: EventMeta {
-            received_time: raw.received_time,
-        },
+pub fn parse_video_balloon_event(raw: RawMessage) -> Result<DonationEvent> {
+    Ok(DonationEvent {
+        meta: EventMeta::new(raw.received_time),
         donation_type: DonationType::VODBalloon,
-        from: normalize_user_id(&body[2]),
-        from_label: body[3].clone(),
-        amount: parse_u32_or_default(&body[4]),
-        fan_club_ordinal: parse_u32_or_default(&body[5]),
-        become_top_fan: body[7] == "1",
+        from: normalize_user_id(raw.field(2, "from")?),
+        from_label: raw.field(3, "from_label")?.to_string(),
+        amount: raw.number(4, "amount", 0)?,
+        fan_club_ordinal: raw.number(5, "fan_club_ordinal", 0)?,
+        become_top_fan: raw.field(7, "become_top_fan")? == "1",
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn raw_message(body: &[&str]) -> RawMessage {
+        RawMessage {
+            code: 87,
+            ret_code: 0,
+            body: body.iter().map(|s| s.to_string()).collect(),
+            received_time: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_short_body_returns_parse_error() {
+        let err = parse_ad_balloon_event(raw_message(&["0", "1", "user", "label"])).unwrap_err();
+
+        match err {
+            crate::Error::ParseError {
+                code,
+                field,
+                body_len,
+            } => {
+                assert_eq!(code, 87);
+                assert_eq!(field, "amount");
+                assert_eq!(body_len, 4);
+            }
+            e => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[test]
+    fn test_invalid_amount_returns_parse_error() {
+        let body = ["0", "user", "label", "abc", "", "", "", "", "0"];
+        let err = parse_balloon_event(raw_message(&body)).unwrap_err();
+        assert!(matches!(err, crate::Error::ParseError { field, .. } if field == "amount"));
+
+        // 빈 필드는 0으로 처리합니다.
+        let body = ["0", "user", "label", "10", "", "", "", "", "0"];
+        let event = parse_balloon_event(raw_message(&body)).unwrap();
+        assert_eq!(event.amount, 10);
+        assert_eq!(event.fan_club_ordinal, 0);
     }
 }
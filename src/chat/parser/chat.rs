@@ -1,59 +1,60 @@
-use crate::chat::{
-    ChatEvent,
-    constants::chat_message_fields,
-    events::EventMeta,
-    parser::{raw::RawMessage, user::parse_user_status, util::normalize_user_id},
-    types::{ChatType, User, UserSubscribe},
+use crate::{
+    Result,
+    chat::{
+        ChatEvent,
+        constants::chat_message_fields,
+        events::EventMeta,
+        parser::{raw::RawMessage, user::parse_user_status, util::normalize_user_id},
+        types::{ChatType, User, UserSubscribe},
+    },
 };
 
-pub fn parse_chat_event(raw: RawMessage) -> ChatEvent {
-    let body = raw.body;
-    let sub = parse_subscribe(&body);
+pub fn parse_chat_event(raw: RawMessage) -> Result<ChatEvent> {
+    let sub = parse_subscribe(&raw)?;
+    let flags = raw.field(chat_message_fields::FLAGS, "flags")?;
 
-    ChatEvent {
-        meta: EventMeta {
-            received_time: raw.received_time,
-        },
+    Ok(ChatEvent {
+        meta: EventMeta::new(raw.received_time),
         chat_type: ChatType::Common,
-        comment: body[chat_message_fields::CONTENT].clone().replace("\r", ""),
+        comment: raw
+            .field(chat_message_fields::CONTENT, "comment")?
+            .replace("\r", ""),
         user: User {
-            id: normalize_user_id(&body[chat_message_fields::USER_ID]),
-            label: body[chat_message_fields::USER_NICK].clone(),
-            status: parse_user_status(&body[chat_message_fields::FLAGS]),
+            id: normalize_user_id(raw.field(chat_message_fields::USER_ID, "user_id")?),
+            label: raw
+                .field(chat_message_fields::USER_NICK, "user_nick")?
+                .to_string(),
+            status: parse_user_status(flags).ok_or_else(|| raw.invalid("flags"))?,
             subscribe: Some(sub),
         },
         is_admin: false,
         emoticon: None,
-    }
+    })
 }
 
-pub fn parse_manager_chat_event(raw: RawMessage) -> ChatEvent {
-    let body = raw.body;
+pub fn parse_manager_chat_event(raw: RawMessage) -> Result<ChatEvent> {
+    let flags = raw.field(5, "flags")?;
 
-    ChatEvent {
-        meta: EventMeta {
-            received_time: raw.received_time,
-        },
+    Ok(ChatEvent {
+        meta: EventMeta::new(raw.received_time),
         chat_type: ChatType::Manager,
-        comment: body[chat_message_fields::CONTENT].clone().replace("\r", ""),
+        comment: raw
+            .field(chat_message_fields::CONTENT, "comment")?
+            .replace("\r", ""),
         user: User {
-            id: normalize_user_id(&body[chat_message_fields::USER_ID]),
-            label: body[4].clone(),
-            status: parse_user_status(&body[5]),
+            id: normalize_user_id(raw.field(chat_message_fields::USER_ID, "user_id")?),
+            label: raw.field(4, "user_nick")?.to_string(),
+            status: parse_user_status(flags).ok_or_else(|| raw.invalid("flags"))?,
             subscribe: None,
         },
         emoticon: None,
-        is_admin: body[2] == "1",
-    }
+        is_admin: raw.field(2, "is_admin")? == "1",
+    })
 }
 
-fn parse_subscribe(body: &Vec<String>) -> UserSubscribe {
-    UserSubscribe {
-        acc: body[chat_message_fields::ACC_SUBSCRIBE]
-            .parse::<u32>()
-            .unwrap_or_default(),
-        current: body[chat_message_fields::SUBSCRIBE]
-            .parse::<u32>()
-            .unwrap_or_default(),
-    }
+fn parse_subscribe(raw: &RawMessage) -> Result<UserSubscribe> {
+    Ok(UserSubscribe {
+        acc: raw.number(chat_message_fields::ACC_SUBSCRIBE, "acc_subscribe", 0)?,
+        current: raw.number(chat_message_fields::SUBSCRIBE, "subscribe", 0)?,
+    })
 }
@@ -7,4 +7,16 @@ pub mod freeze_target_flags {
     pub const MANAGER: u32 = 1 << 9;
 }
 
+use crate::chat::types::FreezeTarget;
+
+// 얼리기 대상, 플래그 순서대로 FreezeEvent::targets에 담깁니다.
+pub const FREEZE_TARGETS: [FreezeTarget; 6] = [
+    FreezeTarget::Normal,
+    FreezeTarget::Fan,
+    FreezeTarget::Supporter,
+    FreezeTarget::TopFan,
+    FreezeTarget::Follower,
+    FreezeTarget::Manager,
+];
+
 pub const SUPER_USERS: [&str; 5] = ["streamer", "manager", "operator", "operator", "cleaner"];
@@ -1,39 +1,40 @@
-use crate::chat::{
-    ChatEvent,
-    events::EventMeta,
-    parser::{raw::RawMessage, user::parse_user_status, util::normalize_user_id},
-    types::{ChatType, Emoticon, User, UserSubscribe},
+use crate::{
+    Result,
+    chat::{
+        ChatEvent,
+        events::EventMeta,
+        parser::{raw::RawMessage, user::parse_user_status, util::normalize_user_id},
+        types::{ChatType, Emoticon, User, UserSubscribe},
+    },
 };
 
-pub fn parse_emoticon_event(raw: RawMessage) -> ChatEvent {
-    let body = raw.body;
-    let sub = parse_subscribe(&body);
+pub fn parse_emoticon_event(raw: RawMessage) -> Result<ChatEvent> {
+    let sub = parse_subscribe(&raw)?;
+    let flags = raw.field(7, "flags")?;
 
-    ChatEvent {
-        meta: EventMeta {
-            received_time: raw.received_time,
-        },
+    Ok(ChatEvent {
+        meta: EventMeta::new(raw.received_time),
         chat_type: ChatType::Emoticon,
-        comment: body[1].clone().replace("\r", ""),
+        comment: raw.field(1, "comment")?.replace("\r", ""),
         user: User {
-            id: normalize_user_id(&body[5]),
-            label: body[6].clone(),
-            status: parse_user_status(&body[7]),
+            id: normalize_user_id(raw.field(5, "user_id")?),
+            label: raw.field(6, "user_nick")?.to_string(),
+            status: parse_user_status(flags).ok_or_else(|| raw.invalid("flags"))?,
             subscribe: Some(sub),
         },
         emoticon: Some(Emoticon {
-            id: body[2].clone(),
-            number: body[3].clone(),
-            ext: body[11].clone(),
-            version: body[4].clone(),
+            id: raw.field(2, "emoticon_id")?.to_string(),
+            number: raw.field(3, "emoticon_number")?.to_string(),
+            ext: raw.field(11, "emoticon_ext")?.to_string(),
+            version: raw.field(4, "emoticon_version")?.to_string(),
         }),
         is_admin: false,
-    }
+    })
 }
 
-fn parse_subscribe(body: &Vec<String>) -> UserSubscribe {
-    UserSubscribe {
-        acc: body[15].parse::<u32>().unwrap_or_default(),
-        current: body[12].parse::<u32>().unwrap_or_default(),
-    }
+fn parse_subscribe(raw: &RawMessage) -> Result<UserSubscribe> {
+    Ok(UserSubscribe {
+        acc: raw.number(15, "acc_subscribe", 0)?,
+        current: raw.number(12, "subscribe", 0)?,
+    })
 }
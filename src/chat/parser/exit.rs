@@ -1,32 +1,32 @@
-use crate::chat::{
-    events::{EventMeta, UserEvent},
-    parser::{raw::RawMessage, user::parse_user_status, util::normalize_user_id},
-    types::User,
+use crate::{
+    Result,
+    chat::{
+        events::{EventMeta, UserEvent},
+        parser::{raw::RawMessage, user::parse_user_status, util::normalize_user_id},
+        types::User,
+    },
 };
 
 // bool: 강제퇴장 여부
-pub fn parse_exit_event(raw: RawMessage) -> Option<(bool, UserEvent)> {
-    let body = raw.body;
-
+pub fn parse_exit_event(raw: RawMessage) -> Result<Option<(bool, UserEvent)>> {
     // "1"인 경우는 특정되지 않은 이벤트
-    if body[0] == "1" || body.len() < 6 {
-        return None;
+    if raw.body.len() < 6 || raw.body[0] == "1" {
+        return Ok(None);
     }
 
-    let is_kick = body[3] != "1";
+    let is_kick = raw.field(3, "is_kick")? != "1";
+    let flags = raw.field(5, "flags")?;
 
-    Some((
+    Ok(Some((
         is_kick,
         UserEvent {
-            meta: EventMeta {
-                received_time: raw.received_time,
-            },
+            meta: EventMeta::new(raw.received_time),
             user: User {
-                id: normalize_user_id(&body[1]),
-                label: body[2].clone(),
-                status: parse_user_status(&body[5]),
+                id: normalize_user_id(raw.field(1, "user_id")?),
+                label: raw.field(2, "user_nick")?.to_string(),
+                status: parse_user_status(flags).ok_or_else(|| raw.invalid("flags"))?,
                 subscribe: None,
             },
         },
-    ))
+    )))
 }
@@ -1,50 +1,32 @@
-use crate::chat::{
-    events::{EventMeta, FreezeEvent},
-    parser::{constants::freeze_target_flags, raw::RawMessage, util::is},
+use crate::{
+    Result,
+    chat::{
+        events::{EventMeta, FreezeEvent},
+        parser::{constants::FREEZE_TARGETS, raw::RawMessage, util::is},
+    },
 };
 
-pub fn parse_freeze_event(raw: RawMessage) -> FreezeEvent {
-    let body = raw.body;
-
-    FreezeEvent {
-        meta: EventMeta {
-            received_time: raw.received_time,
-        },
-        freezed: body[0] != "0",
-        limit_balloons: body[3].parse::<u32>().unwrap_or(0),
-        limit_subscription_month: body[4].parse::<u32>().unwrap_or(0),
-        targets: parse_freeze_targets(&body[2]),
-    }
+pub fn parse_freeze_event(raw: RawMessage) -> Result<FreezeEvent> {
+    let targets =
+        parse_freeze_targets(raw.field(2, "targets")?).ok_or_else(|| raw.invalid("targets"))?;
+
+    Ok(FreezeEvent {
+        meta: EventMeta::new(raw.received_time),
+        freezed: raw.field(0, "freezed")? != "0",
+        limit_balloons: raw.number(3, "limit_balloons", 0)?,
+        limit_subscription_month: raw.number(4, "limit_subscription_month", 0)?,
+        targets,
+    })
 }
 
-fn parse_freeze_targets(flag_str: &str) -> Vec<String> {
-    let mut targets: Vec<String> = Vec::new();
-
-    let flag = flag_str.parse::<u32>().unwrap();
-
-    if is(flag, freeze_target_flags::NORMAL) {
-        targets.push("BJ".to_string());
-    }
-
-    if is(flag, freeze_target_flags::FAN) {
-        targets.push("FAN".to_string());
-    }
-
-    if is(flag, freeze_target_flags::SUPPORTER) {
-        targets.push("SUPPORTER".to_string());
-    }
-
-    if is(flag, freeze_target_flags::TOP_FAN) {
-        targets.push("TOP_FAN".to_string());
-    }
-
-    if is(flag, freeze_target_flags::FOLLOWER) {
-        targets.push("FOLLOWER".to_string());
-    }
-
-    if is(flag, freeze_target_flags::MANAGER) {
-        targets.push("MANGER".to_string());
-    }
+fn parse_freeze_targets(flag_str: &str) -> Option<Vec<String>> {
+    let flag = flag_str.parse::<u32>().ok()?;
 
-    targets
+    Some(
+        FREEZE_TARGETS
+            .iter()
+            .filter(|target| is(flag, target.flag()))
+            .map(|target| target.as_str().to_string())
+            .collect(),
+    )
 }
@@ -1,53 +1,44 @@
-use crate::chat::{
-    events::{EventMeta, GiftEvent},
-    parser::{raw::RawMessage, util::normalize_user_id},
-    types::GiftType,
+use crate::{
+    Result,
+    chat::{
+        events::{EventMeta, GiftEvent},
+        parser::{raw::RawMessage, util::normalize_user_id},
+        types::GiftType,
+    },
 };
 
-pub fn parse_subscribe_gift_event(raw: RawMessage) -> GiftEvent {
-    let body = raw.body;
-
-    GiftEvent {
-        meta: EventMeta {
-            received_time: raw.received_time,
-        },
+pub fn parse_subscribe_gift_event(raw: RawMessage) -> Result<GiftEvent> {
+    Ok(GiftEvent {
+        meta: EventMeta::new(raw.received_time),
         gift_type: GiftType::Subscription,
-        sender_id: normalize_user_id(&body[1]),
-        sender_label: body[2].clone(),
-        receiver_id: normalize_user_id(&body[3]),
-        receiver_label: body[4].clone(),
-        gift_code: body[7].clone(),
-    }
+        sender_id: normalize_user_id(raw.field(1, "sender_id")?),
+        sender_label: raw.field(2, "sender_label")?.to_string(),
+        receiver_id: normalize_user_id(raw.field(3, "receiver_id")?),
+        receiver_label: raw.field(4, "receiver_label")?.to_string(),
+        gift_code: raw.field(7, "gift_code")?.to_string(),
+    })
 }
 
-pub fn parse_quickview_gift_event(raw: RawMessage) -> GiftEvent {
-    let body = raw.body;
-
-    GiftEvent {
-        meta: EventMeta {
-            received_time: raw.received_time,
-        },
+pub fn parse_quickview_gift_event(raw: RawMessage) -> Result<GiftEvent> {
+    Ok(GiftEvent {
+        meta: EventMeta::new(raw.received_time),
         gift_type: GiftType::QuickView,
-        sender_id: normalize_user_id(&body[1]),
-        sender_label: body[2].clone(),
-        receiver_id: normalize_user_id(&body[3]),
-        receiver_label: body[4].clone(),
-        gift_code: body[5].clone(),
-    }
+        sender_id: normalize_user_id(raw.field(1, "sender_id")?),
+        sender_label: raw.field(2, "sender_label")?.to_string(),
+        receiver_id: normalize_user_id(raw.field(3, "receiver_id")?),
+        receiver_label: raw.field(4, "receiver_label")?.to_string(),
+        gift_code: raw.field(5, "gift_code")?.to_string(),
+    })
 }
 
-pub fn parse_ogq_gift_event(raw: RawMessage) -> GiftEvent {
-    let body = raw.body;
-
-    GiftEvent {
-        meta: EventMeta {
-            received_time: raw.received_time,
-        },
+pub fn parse_ogq_gift_event(raw: RawMessage) -> Result<GiftEvent> {
+    Ok(GiftEvent {
+        meta: EventMeta::new(raw.received_time),
         gift_type: GiftType::OGQ,
-        sender_id: normalize_user_id(&body[1]),
-        sender_label: body[2].clone(),
-        receiver_id: normalize_user_id(&body[3]),
-        receiver_label: body[4].clone(),
-        gift_code: body[5].clone(),
-    }
+        sender_id: normalize_user_id(raw.field(1, "sender_id")?),
+        sender_label: raw.field(2, "sender_label")?.to_string(),
+        receiver_id: normalize_user_id(raw.field(3, "receiver_id")?),
+        receiver_label: raw.field(4, "receiver_label")?.to_string(),
+        gift_code: raw.field(5, "gift_code")?.to_string(),
+    })
 }
@@ -1,19 +1,18 @@
-use crate::chat::{
-    events::{EventMeta, SimplifiedUserEvent},
-    parser::{raw::RawMessage, util::normalize_user_id},
+use crate::{
+    Result,
+    chat::{
+        events::{EventMeta, SimplifiedUserEvent},
+        parser::{raw::RawMessage, util::normalize_user_id},
+    },
 };
 
-pub fn parse_kick_cancel_event(raw: RawMessage) -> Option<SimplifiedUserEvent> {
-    let body = raw.body;
-
-    if body[0] != "1" {
-        return None;
+pub fn parse_kick_cancel_event(raw: RawMessage) -> Result<Option<SimplifiedUserEvent>> {
+    if raw.field(0, "kick_cancel")? != "1" {
+        return Ok(None);
     }
 
-    Some(SimplifiedUserEvent {
-        meta: EventMeta {
-            received_time: raw.received_time,
-        },
-        user_id: normalize_user_id(&body[1]),
-    })
+    Ok(Some(SimplifiedUserEvent {
+        meta: EventMeta::new(raw.received_time),
+        user_id: normalize_user_id(raw.field(1, "user_id")?),
+    }))
 }
@@ -20,10 +20,9 @@ use crate::{
 };
 
 pub fn parse_mission_event(raw: RawMessage) -> Result<(MissionParser, Box<dyn Any>)> {
-    let body = &raw.body;
-    let raw_json = body[0].clone();
+    let raw_json = raw.field(0, "json")?.to_string();
     let abs_json: AbstractMissionData =
-        serde_json::from_str(&raw_json).map_err(|err| Error::InternalChannel(err.to_string()))?;
+        serde_json::from_str(&raw_json).map_err(Error::SerdeJson)?;
 
     let message_type = abs_json.message_type.as_str();
 
@@ -44,18 +43,15 @@ pub fn parse_mission_event(raw: RawMessage) -> Result<(MissionParser, Box<dyn An
             let e = parse_challenge_result(raw, &raw_json)?;
             Ok((MissionParser::ChallengeNotice, Box::new(e)))
         }
-        _ => Err(Error::InternalChannel("미션 이벤트 파싱 실패".to_string())),
+        _ => Err(raw.invalid("type")),
     }
 }
 
 fn parse_gift_event(raw: &RawMessage, body: &str, message_type: &str) -> Result<MissionEvent> {
-    let p: MissionGiftPayload = serde_json::from_str(body)
-        .map_err(|_| Error::InternalChannel("미션 페이로드 파싱오류".to_string()))?;
+    let p: MissionGiftPayload = serde_json::from_str(body).map_err(Error::SerdeJson)?;
 
     Ok(MissionEvent {
-        meta: EventMeta {
-            received_time: raw.received_time,
-        },
+        meta: EventMeta::new(raw.received_time),
         from: normalize_user_id(&p.user_id),
         from_label: p.label,
         amount: p.amount as u32,
@@ -72,13 +68,10 @@ fn parse_gift_total_event(
     body: &str,
     message_type: &str,
 ) -> Result<MissionTotalEvent> {
-    let p: MissionGiftTotalPayload = serde_json::from_str(body)
-        .map_err(|_| Error::InternalChannel("미션 페이로드 파싱오류".to_string()))?;
+    let p: MissionGiftTotalPayload = serde_json::from_str(body).map_err(Error::SerdeJson)?;
 
     Ok(MissionTotalEvent {
-        meta: EventMeta {
-            received_time: raw.received_time,
-        },
+        meta: EventMeta::new(raw.received_time),
         mission_type: if message_type == "CHALLENGE_SETTLE" {
             MissionType::Challenge
         } else {
@@ -89,13 +82,10 @@ fn parse_gift_total_event(
 }
 
 fn parse_battle_result(raw: RawMessage, body: &str) -> Result<BattleMissionResultEvent> {
-    let p: BattleMissionResultPayload = serde_json::from_str(body)
-        .map_err(|_| Error::InternalChannel("미션 페이로드 파싱오류".to_string()))?;
+    let p: BattleMissionResultPayload = serde_json::from_str(body).map_err(Error::SerdeJson)?;
 
     Ok(BattleMissionResultEvent {
-        meta: EventMeta {
-            received_time: raw.received_time,
-        },
+        meta: EventMeta::new(raw.received_time),
         is_draw: p.draw,
         winner: p.winner,
         title: p.title,
@@ -103,13 +93,10 @@ fn parse_battle_result(raw: RawMessage, body: &str) -> Result<BattleMissionResul
 }
 
 fn parse_challenge_result(raw: RawMessage, body: &str) -> Result<ChallengeMissionResultEvent> {
-    let p: ChallengeMissionResultPayload = serde_json::from_str(body)
-        .map_err(|_| Error::InternalChannel("미션 페이로드 파싱오류".to_string()))?;
+    let p: ChallengeMissionResultPayload = serde_json::from_str(body).map_err(Error::SerdeJson)?;
 
     Ok(ChallengeMissionResultEvent {
-        meta: EventMeta {
-            received_time: raw.received_time,
-        },
+        meta: EventMeta::new(raw.received_time),
         is_success: p.status == "SUCCESS",
         title: p.title,
     })
@@ -1,6 +1,6 @@
 pub mod balloon;
 pub mod chat;
-mod constants;
+pub(crate) mod constants;
 pub mod emoticon;
 pub mod exit;
 pub mod freeze;
@@ -11,9 +11,10 @@ pub mod mission;
 pub mod mute;
 pub mod notification;
 pub mod raw;
+pub mod room;
 pub mod slow;
 pub mod sticker;
 pub mod subscribe;
 mod types;
-mod user;
-mod util;
+pub(crate) mod user;
+pub(crate) mod util;
@@ -1,27 +1,34 @@
-use crate::chat::{
-    events::{EventMeta, MuteEvent},
-    parser::{constants::SUPER_USERS, raw::RawMessage, user::parse_user_status, util::normalize_user_id},
-    types::User,
+use crate::{
+    Result,
+    chat::{
+        events::{EventMeta, MuteEvent},
+        parser::{
+            constants::SUPER_USERS, raw::RawMessage, user::parse_user_status,
+            util::normalize_user_id,
+        },
+        types::User,
+    },
 };
 
 // bool: 강제퇴장 여부
-pub fn parse_mute_event(raw: RawMessage) -> MuteEvent {
-    let body = raw.body;
+pub fn parse_mute_event(raw: RawMessage) -> Result<MuteEvent> {
+    let flags = raw.field(1, "flags")?;
+    let superuser_type = SUPER_USERS
+        .get(raw.number::<usize>(5, "superuser_type", 0)?)
+        .ok_or_else(|| raw.invalid("superuser_type"))?;
 
-    MuteEvent {
-        meta: EventMeta {
-            received_time: raw.received_time,
-        },
+    Ok(MuteEvent {
+        meta: EventMeta::new(raw.received_time),
         user: User {
-            id: normalize_user_id(&body[0]),
-            label: body[7].clone(),
-            status: parse_user_status(&body[1]),
+            id: normalize_user_id(raw.field(0, "user_id")?),
+            label: raw.field(7, "user_nick")?.to_string(),
+            status: parse_user_status(flags).ok_or_else(|| raw.invalid("flags"))?,
             subscribe: None,
         },
-        superuser_type: SUPER_USERS[body[5].parse::<usize>().unwrap_or(0)].to_string(),
-        by: normalize_user_id(&body[4]),
+        superuser_type: superuser_type.to_string(),
+        by: normalize_user_id(raw.field(4, "by")?),
         message: "".to_string(),
-        seconds: body[2].parse::<u32>().unwrap_or(0),
-        counts: body[3].parse::<u32>().unwrap_or(1),
-    }
+        seconds: raw.number(2, "seconds", 0)?,
+        counts: raw.number(3, "counts", 1)?,
+    })
 }
@@ -1,16 +1,15 @@
-use crate::chat::{
-    events::{EventMeta, NotificationEvent},
-    parser::raw::RawMessage,
+use crate::{
+    Result,
+    chat::{
+        events::{EventMeta, NotificationEvent},
+        parser::raw::RawMessage,
+    },
 };
 
-pub fn parse_notification_event(raw: RawMessage) -> NotificationEvent {
-    let body = raw.body;
-
-    NotificationEvent {
-        meta: EventMeta {
-            received_time: raw.received_time,
-        },
-        show: body[1] == "1",
-        message: body[3].clone(),
-    }
+pub fn parse_notification_event(raw: RawMessage) -> Result<NotificationEvent> {
+    Ok(NotificationEvent {
+        meta: EventMeta::new(raw.received_time),
+        show: raw.field(1, "show")? == "1",
+        message: raw.field(3, "message")?.to_string(),
+    })
 }
@@ -1,48 +1,186 @@
+use std::str::FromStr;
+
 use chrono::{DateTime, Utc};
 
-use crate::chat::constants::{SEPARATOR_U8, message_codes::MessageCode};
+use crate::{
+    Error, Result,
+    chat::constants::{SEPARATOR_U8, STARTER_VEC, message_codes::MessageCode},
+    error::FrameError,
+};
 
+/// 헤더와 본문 필드로 나눈 채팅 패킷입니다.
 #[derive(Debug)]
 pub struct RawMessage {
     pub code: MessageCode,
-    pub _red_code: u32,
+    /// 헤더의 결과 코드, 0이 아니면 서버가 명령을 거부한 응답입니다.
+    pub ret_code: u32,
     pub body: Vec<String>,
     pub received_time: DateTime<Utc>,
 }
 
+impl RawMessage {
+    /// body의 index번째 필드를 가져옵니다. 필드가 없으면 ParseError를 반환합니다.
+    pub fn field(&self, index: usize, name: &str) -> Result<&str> {
+        self.body
+            .get(index)
+            .map(String::as_str)
+            .ok_or_else(|| self.invalid(name))
+    }
+
+    /// body의 index번째 필드를 숫자로 가져옵니다.
+    /// 빈 필드는 `default`로 처리하고, 숫자가 아닌 값은 ParseError를 반환합니다.
+    pub fn number<T: FromStr>(&self, index: usize, name: &str, default: T) -> Result<T> {
+        let value = self.field(index, name)?;
+        if value.is_empty() {
+            return Ok(default);
+        }
+        value.parse().map_err(|_| self.invalid(name))
+    }
+
+    /// 필드가 없거나 값이 올바르지 않을 때 반환할 ParseError를 생성합니다.
+    pub fn invalid(&self, name: &str) -> Error {
+        Error::ParseError {
+            code: self.code,
+            field: name.to_string(),
+            body_len: self.body.len(),
+        }
+    }
+}
+
+/// 헤더 길이: STARTER(2) + code(4) + body length(6) + ret code(2)
+pub const HEADER_LEN: usize = 14;
+
 struct MessageHeader {
     code: MessageCode,
+    body_len: usize,
     ret_code: u32,
 }
 
-pub fn parse_message(data: &[u8]) -> Result<RawMessage, String> {
-    let now = Utc::now();
-
-    let header_bytes = &data[0..14];
+/// 하나의 완성된 패킷을 해석합니다.
+///
+/// 헤더의 시작 바이트와 숫자 필드를 검사하고, 선언된 본문 길이가 실제 길이와 같은지 확인합니다.
+pub fn parse_message(data: &[u8]) -> Result<RawMessage> {
+    parse_message_at(data, Utc::now())
+}
 
-    let header = parse_header(header_bytes)?;
+/// 수신 시간을 지정하여 패킷을 해석합니다. 녹화된 패킷을 재생할 때 사용합니다.
+pub(crate) fn parse_message_at(data: &[u8], received_time: DateTime<Utc>) -> Result<RawMessage> {
+    let header = parse_header(data)?;
 
-    let body = &data[14..];
+    let body = &data[HEADER_LEN..];
+    if body.len() != header.body_len {
+        return Err(FrameError::BodyLengthMismatch {
+            declared: header.body_len,
+            actual: body.len(),
+        }
+        .into());
+    }
 
     Ok(RawMessage {
         code: header.code,
-        _red_code: header.ret_code,
+        ret_code: header.ret_code,
         body: parse_body(body),
-        received_time: now,
+        received_time,
     })
 }
 
-fn parse_header(header: &[u8]) -> Result<MessageHeader, String> {
-    if header.len() != 14 {
-        return Err("Invalid header length".to_string());
+fn parse_header(data: &[u8]) -> std::result::Result<MessageHeader, FrameError> {
+    if data.len() < HEADER_LEN {
+        return Err(FrameError::TooShort { len: data.len() });
+    }
+
+    if !data.starts_with(STARTER_VEC) {
+        return Err(FrameError::InvalidStarter);
     }
 
     Ok(MessageHeader {
-        code: parse_bytes_to_u32(&header[2..6]),
-        ret_code: parse_bytes_to_u32(&header[12..14]),
+        code: parse_header_digits(&data[2..6], "code")? as MessageCode,
+        body_len: parse_header_digits(&data[6..12], "body_len")?,
+        ret_code: parse_header_digits(&data[12..14], "ret_code")? as u32,
     })
 }
 
+/// 헤더의 고정 길이 숫자 필드를 해석합니다. ASCII 숫자 외의 바이트가 있으면 실패합니다.
+fn parse_header_digits(
+    bytes: &[u8],
+    field: &'static str,
+) -> std::result::Result<usize, FrameError> {
+    if bytes.is_empty() || !bytes.iter().all(u8::is_ascii_digit) {
+        return Err(FrameError::InvalidHeader { field });
+    }
+
+    Ok(bytes
+        .iter()
+        .fold(0, |acc, b| acc * 10 + (b - b'0') as usize))
+}
+
+/// WebSocket 메시지에서 패킷 단위로 데이터를 잘라냅니다.
+///
+/// 하나의 메시지에 여러 패킷이 이어져 있거나, 하나의 패킷이 여러 메시지로 나뉘어 와도
+/// 헤더에 선언된 본문 길이를 기준으로 패킷을 복원합니다.
+/// 해석할 수 없는 바이트는 다음 시작 바이트 전까지 하나의 조각으로 내보내며,
+/// 이 조각은 `parse_message`에서 `FrameError`로 보고됩니다.
+#[derive(Debug, Default)]
+pub struct FrameDecoder {
+    buffer: Vec<u8>,
+}
+
+impl FrameDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 수신한 데이터를 버퍼에 추가하고, 완성된 패킷(또는 잘못된 조각)들을 반환합니다.
+    /// 아직 완성되지 않은 패킷은 다음 호출까지 버퍼에 남겨둡니다.
+    pub fn decode(&mut self, data: &[u8]) -> Vec<Vec<u8>> {
+        self.buffer.extend_from_slice(data);
+
+        let mut frames = Vec::new();
+        while let Some(len) = self.next_frame_len() {
+            frames.push(self.buffer.drain(..len).collect());
+        }
+        frames
+    }
+
+    /// 버퍼 앞에서 잘라낼 수 있는 조각의 길이를 계산합니다. 데이터가 더 필요하면 None입니다.
+    fn next_frame_len(&self) -> Option<usize> {
+        let buf = &self.buffer;
+        if buf.is_empty() {
+            return None;
+        }
+
+        if !buf.starts_with(STARTER_VEC) {
+            // 시작 바이트의 첫 바이트만 도착했을 수도 있으므로 기다립니다.
+            if buf == &STARTER_VEC[..1] {
+                return None;
+            }
+            return Some(self.garbage_len(0));
+        }
+
+        if buf.len() < HEADER_LEN {
+            return None;
+        }
+
+        match parse_header_digits(&buf[6..12], "body_len") {
+            Ok(body_len) => {
+                let total = HEADER_LEN + body_len;
+                (buf.len() >= total).then_some(total)
+            }
+            // 길이를 알 수 없는 패킷은 다음 시작 바이트까지를 하나의 조각으로 취급합니다.
+            Err(_) => Some(self.garbage_len(STARTER_VEC.len())),
+        }
+    }
+
+    /// from 이후에 나타나는 다음 시작 바이트 위치를 찾습니다. 없으면 버퍼 전체 길이입니다.
+    fn garbage_len(&self, from: usize) -> usize {
+        self.buffer[from..]
+            .windows(STARTER_VEC.len())
+            .position(|w| w == STARTER_VEC)
+            .map(|pos| from + pos)
+            .unwrap_or(self.buffer.len())
+    }
+}
+
 fn parse_body(body: &[u8]) -> Vec<String> {
     // 입력 데이터가 너무 짧으면(헤더만 있거나 비어있으면) 빈 벡터를 반환합니다.
     if body.len() < 2 {
@@ -53,7 +191,10 @@ fn parse_body(body: &[u8]) -> Vec<String> {
     let data_to_process = &body[1..];
 
     // 구분자 개수를 미리 세어서 Vec 용량을 할당합니다.
-    let separator_count = data_to_process.iter().filter(|&&b| b == SEPARATOR_U8).count();
+    let separator_count = data_to_process
+        .iter()
+        .filter(|&&b| b == SEPARATOR_U8)
+        .count();
     let mut result = Vec::with_capacity(separator_count + 1);
 
     // 데이터를 구분자로 분할하고, 각 조각을 문자열로 변환한 뒤, 벡터로 수집합니다.
@@ -68,17 +209,110 @@ fn parse_body(body: &[u8]) -> Vec<String> {
             }
         }
     }
-    
+
     result
 }
 
-fn parse_bytes_to_u32(bytes: &[u8]) -> u32 {
-    // UTF-8 바이트를 직접 파싱하여 불필요한 String 할당을 피합니다.
-    match std::str::from_utf8(bytes) {
-        Ok(s) => s.parse::<u32>().unwrap_or(0),
-        Err(_) => {
-            // 유효하지 않은 UTF-8인 경우에만 lossy conversion 사용
-            String::from_utf8_lossy(bytes).parse::<u32>().unwrap_or(0)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chat::{commands::MessageType, formatter::bundle};
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_short_and_malformed_frames() {
+        assert!(matches!(
+            parse_message(b"\x1b\t0005"),
+            Err(Error::Frame(FrameError::TooShort { len: 6 }))
+        ));
+        assert!(matches!(
+            parse_message(b"{\"text\": \"frame\"}"),
+            Err(Error::Frame(FrameError::InvalidStarter))
+        ));
+        assert!(matches!(
+            parse_message(b"\x1b\t00a500000000"),
+            Err(Error::Frame(FrameError::InvalidHeader { field: "code" }))
+        ));
+        assert!(matches!(
+            parse_message(b"\x1b\t00050000030012"),
+            Err(Error::Frame(FrameError::BodyLengthMismatch {
+                declared: 3,
+                actual: 2
+            }))
+        ));
+    }
+
+    #[test]
+    fn test_decoder_splits_concatenated_packets() {
+        let first = bundle(MessageType::Chat, b"\x0chello\x0c");
+        let second = bundle(MessageType::Ping, b"");
+        let mut data = first.clone();
+        data.extend_from_slice(&second);
+
+        let mut decoder = FrameDecoder::new();
+        assert_eq!(decoder.decode(&data[..5]), Vec::<Vec<u8>>::new());
+        assert_eq!(decoder.decode(&data[5..]), vec![first, second]);
+        assert_eq!(decoder.buffer.len(), 0);
+    }
+
+    #[test]
+    fn test_decoder_emits_garbage_until_next_starter() {
+        let packet = bundle(MessageType::Ping, b"");
+        let mut data = b"garbage".to_vec();
+        data.extend_from_slice(&packet);
+
+        let frames = FrameDecoder::new().decode(&data);
+        assert_eq!(frames, vec![b"garbage".to_vec(), packet]);
+        assert!(parse_message(&frames[0]).is_err());
+    }
+
+    fn packet() -> impl Strategy<Value = Vec<u8>> {
+        // 본문에는 시작 바이트(ESC)가 나타나지 않습니다.
+        (0u32..10000, prop::collection::vec(0u8..27, 0..64)).prop_map(|(code, body)| {
+            let mut packet = format!("\x1b\t{code:04}{:06}00", body.len()).into_bytes();
+            packet.extend_from_slice(&body);
+            packet
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn prop_parse_message_never_panics(data in prop::collection::vec(any::<u8>(), 0..64)) {
+            let _ = parse_message(&data);
+        }
+
+        #[test]
+        fn prop_decoder_never_panics(chunks in prop::collection::vec(prop::collection::vec(any::<u8>(), 0..32), 0..8)) {
+            let mut decoder = FrameDecoder::new();
+            for chunk in &chunks {
+                for frame in decoder.decode(chunk) {
+                    let _ = parse_message(&frame);
+                }
+            }
+        }
+
+        #[test]
+        fn prop_decoder_restores_split_packets(
+            packets in prop::collection::vec(packet(), 1..8),
+            splits in prop::collection::vec(any::<prop::sample::Index>(), 0..8),
+        ) {
+            let data = packets.concat();
+            let mut points: Vec<usize> = splits.iter().map(|i| i.index(data.len())).collect();
+            points.sort_unstable();
+
+            let mut decoder = FrameDecoder::new();
+            let mut frames = Vec::new();
+            let mut start = 0;
+            for point in points.into_iter().chain([data.len()]) {
+                frames.extend(decoder.decode(&data[start..point]));
+                start = point;
+            }
+
+            prop_assert_eq!(decoder.buffer.len(), 0);
+            prop_assert_eq!(&frames, &packets);
+            for frame in &frames {
+                prop_assert!(parse_message(frame).is_ok());
+            }
         }
     }
 }
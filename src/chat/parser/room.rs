@@ -0,0 +1,113 @@
+use crate::{
+    Result,
+    chat::{
+        constants::message_codes,
+        events::{
+            ChatRoomSettingEvent, EnterInfoEvent, EventMeta, ManagerChangeEvent, PollEvent,
+            RankingEvent, ViewerCountEvent,
+        },
+        parser::{raw::RawMessage, user::parse_user_status, util::normalize_user_id},
+        types::ChatRoomSetting,
+    },
+};
+
+pub fn parse_enter_info_event(raw: RawMessage) -> Result<EnterInfoEvent> {
+    Ok(EnterInfoEvent {
+        meta: EventMeta::new(raw.received_time),
+        user_id: normalize_user_id(raw.field(0, "user_id")?),
+        status: raw.body.get(1).and_then(|flags| parse_user_status(flags)),
+        body: raw.body,
+    })
+}
+
+pub fn parse_manager_change_event(raw: RawMessage) -> Result<ManagerChangeEvent> {
+    Ok(ManagerChangeEvent {
+        meta: EventMeta::new(raw.received_time),
+        user_id: normalize_user_id(raw.field(0, "user_id")?),
+        is_manager: raw.field(1, "is_manager")? == "1",
+        body: raw.body,
+    })
+}
+
+pub fn parse_viewer_count_event(raw: RawMessage) -> Result<ViewerCountEvent> {
+    let count = raw
+        .field(0, "count")?
+        .parse::<u32>()
+        .map_err(|_| raw.invalid("count"))?;
+
+    Ok(ViewerCountEvent {
+        meta: EventMeta::new(raw.received_time),
+        count,
+        body: raw.body,
+    })
+}
+
+pub fn parse_ranking_event(raw: RawMessage) -> Result<RankingEvent> {
+    // 순위 목록은 '|'로 구분된 사용자 ID입니다.
+    let user_ids = raw
+        .field(0, "user_ids")?
+        .split('|')
+        .filter(|id| !id.is_empty())
+        .map(normalize_user_id)
+        .collect();
+
+    Ok(RankingEvent {
+        meta: EventMeta::new(raw.received_time),
+        is_subscription: raw.code == message_codes::TOP_FAN_SUB,
+        user_ids,
+        body: raw.body,
+    })
+}
+
+pub fn parse_poll_event(raw: RawMessage) -> Result<PollEvent> {
+    let poll = serde_json::from_str(raw.field(0, "poll")?).ok();
+
+    Ok(PollEvent {
+        meta: EventMeta::new(raw.received_time),
+        poll,
+        body: raw.body,
+    })
+}
+
+pub fn parse_chat_room_setting_event(raw: RawMessage) -> Result<ChatRoomSettingEvent> {
+    let setting = match raw.code {
+        message_codes::ICE_MODE => ChatRoomSetting::Ice,
+        message_codes::CHAT_BLOCK_MODE => ChatRoomSetting::ChatBlock,
+        _ => return Err(raw.invalid("code")),
+    };
+
+    Ok(ChatRoomSettingEvent {
+        meta: EventMeta::new(raw.received_time),
+        setting,
+        enabled: raw.field(0, "enabled")? == "1",
+        body: raw.body,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn raw_message(code: u32, body: &[&str]) -> RawMessage {
+        RawMessage {
+            code,
+            ret_code: 0,
+            body: body.iter().map(|s| s.to_string()).collect(),
+            received_time: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_ranking_and_viewer_count() {
+        let ranking =
+            parse_ranking_event(raw_message(message_codes::TOP_FAN_SUB, &["a|b(2)|", "x"]))
+                .unwrap();
+        assert!(ranking.is_subscription);
+        assert_eq!(ranking.user_ids, vec!["a", "b"]);
+        assert_eq!(ranking.body.len(), 2);
+
+        let err = parse_viewer_count_event(raw_message(message_codes::VIEWER_COUNT, &["many"]));
+        assert!(matches!(err, Err(crate::Error::ParseError { .. })));
+    }
+}
@@ -1,35 +1,27 @@
-use crate::chat::{
-    events::{EventMeta, StickerEvent},
-    parser::{
-        raw::RawMessage,
-        util::{normalize_user_id, parse_u32_or_default},
+use crate::{
+    Result,
+    chat::{
+        events::{EventMeta, StickerEvent},
+        parser::{raw::RawMessage, util::normalize_user_id},
     },
 };
 
-pub fn parse_sticker_event(raw: RawMessage) -> StickerEvent {
-    let body = raw.body;
-
-    StickerEvent {
-        meta: EventMeta {
-            received_time: raw.received_time,
-        },
-        from: normalize_user_id(&body[2]),
-        from_label: body[3].clone(),
-        amount: parse_u32_or_default(&body[7]),
-        supporter_ordinal: parse_u32_or_default(&body[8]),
-    }
+pub fn parse_sticker_event(raw: RawMessage) -> Result<StickerEvent> {
+    Ok(StickerEvent {
+        meta: EventMeta::new(raw.received_time),
+        from: normalize_user_id(raw.field(2, "from")?),
+        from_label: raw.field(3, "from_label")?.to_string(),
+        amount: raw.number(7, "amount", 0)?,
+        supporter_ordinal: raw.number(8, "supporter_ordinal", 0)?,
+    })
 }
 
-pub fn parse_sticker_sub_event(raw: RawMessage) -> StickerEvent {
-    let body = raw.body;
-
-    StickerEvent {
-        meta: EventMeta {
-            received_time: raw.received_time,
-        },
-        from: normalize_user_id(&body[3]),
-        from_label: body[4].clone(),
-        amount: parse_u32_or_default(&body[8]),
-        supporter_ordinal: parse_u32_or_default(&body[9]),
-    }
+pub fn parse_sticker_sub_event(raw: RawMessage) -> Result<StickerEvent> {
+    Ok(StickerEvent {
+        meta: EventMeta::new(raw.received_time),
+        from: normalize_user_id(raw.field(3, "from")?),
+        from_label: raw.field(4, "from_label")?.to_string(),
+        amount: raw.number(8, "amount", 0)?,
+        supporter_ordinal: raw.number(9, "supporter_ordinal", 0)?,
+    })
 }
@@ -15,11 +15,6 @@ pub fn normalize_user_id(user_id: &str) -> String {
     user_id.to_string()
 }
 
-#[inline]
-pub fn parse_u32_or_default(s: &str) -> u32 {
-    s.parse::<u32>().unwrap_or(0)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
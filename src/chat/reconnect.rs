@@ -0,0 +1,209 @@
+use std::time::{Duration, Instant};
+
+/// 연결이 끊어졌을 때 재연결을 시도하는 정책입니다.
+///
+/// 지수 백오프(exponential backoff)에 지터(jitter)를 더해 대기 시간을 계산합니다.
+#[derive(Debug, Clone)]
+pub struct ReconnectPolicy {
+    /// 재연결 사용 여부
+    pub enabled: bool,
+    /// 첫 번째 재연결 대기 시간
+    pub initial_delay: Duration,
+    /// 대기 시간의 상한
+    pub max_delay: Duration,
+    /// 시도마다 대기 시간에 곱해지는 배수
+    pub multiplier: f64,
+    /// 대기 시간에 더해지는 무작위 비율 (0.0 ~ 1.0)
+    pub jitter: f64,
+    /// 최대 재연결 시도 횟수, None이면 무제한
+    pub max_attempts: Option<u32>,
+    /// 연결이 끊긴 뒤 재연결을 포기하기까지의 최대 시간, None이면 무제한
+    pub max_downtime: Option<Duration>,
+    /// 연결이 이 시간 이상 유지된 뒤에 끊겨야 시도 횟수와 단절 시간을 초기화합니다.
+    /// 접속 직후 연결을 끊는 서버에서도 `max_attempts`와 `max_downtime`이 적용되도록 합니다.
+    pub stable_after: Duration,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+            multiplier: 2.0,
+            jitter: 0.2,
+            max_attempts: None,
+            max_downtime: Some(Duration::from_secs(60 * 30)),
+            stable_after: Duration::from_secs(10),
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// 재연결을 하지 않는 정책
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Self::default()
+        }
+    }
+
+    /// 지터를 적용하기 전의 n번째(1부터 시작) 시도 대기 시간을 계산합니다.
+    fn base_delay(&self, attempt: u32) -> Duration {
+        let exp = self.multiplier.powi(attempt.saturating_sub(1) as i32);
+        let delay = self.initial_delay.as_secs_f64() * exp;
+        Duration::from_secs_f64(delay.min(self.max_delay.as_secs_f64()))
+    }
+}
+
+/// 재연결 시도 횟수와 단절 시간을 추적합니다.
+#[derive(Debug)]
+pub(crate) struct Backoff {
+    policy: ReconnectPolicy,
+    attempt: u32,
+    down_since: Option<Instant>,
+    // 현재 연결이 맺어진 시간
+    connected_at: Option<Instant>,
+}
+
+impl Backoff {
+    pub fn new(policy: ReconnectPolicy) -> Self {
+        Self {
+            policy,
+            attempt: 0,
+            down_since: None,
+            connected_at: None,
+        }
+    }
+
+    /// 시도 횟수와 단절 시간을 초기화합니다.
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+        self.down_since = None;
+        self.connected_at = None;
+    }
+
+    /// 서버와 연결이 맺어진 시간을 기록합니다.
+    pub fn connected(&mut self) {
+        self.connected_at = Some(Instant::now());
+    }
+
+    /// 연결이 끊어졌을 때 호출합니다.
+    /// 연결이 `stable_after` 이상 유지되었을 때만 상태를 초기화합니다.
+    pub fn disconnected(&mut self) {
+        if self
+            .connected_at
+            .take()
+            .is_some_and(|connected_at| connected_at.elapsed() >= self.policy.stable_after)
+        {
+            self.reset();
+        }
+    }
+
+    /// 다음 재연결 시도 번호와 대기 시간을 반환합니다.
+    /// 정책상 더 이상 시도하지 않아야 하면 None을 반환합니다.
+    pub fn next_delay(&mut self) -> Option<(u32, Duration)> {
+        if !self.policy.enabled {
+            return None;
+        }
+
+        let down_since = *self.down_since.get_or_insert_with(Instant::now);
+
+        if let Some(max_attempts) = self.policy.max_attempts
+            && self.attempt >= max_attempts
+        {
+            return None;
+        }
+
+        self.attempt += 1;
+        let base = self.policy.base_delay(self.attempt);
+        let jitter = base.mul_f64(self.policy.jitter.clamp(0.0, 1.0) * fastrand::f64());
+        let delay = base + jitter;
+
+        if let Some(max_downtime) = self.policy.max_downtime
+            && down_since.elapsed() + delay > max_downtime
+        {
+            return None;
+        }
+
+        Some((self.attempt, delay))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> ReconnectPolicy {
+        ReconnectPolicy {
+            jitter: 0.0,
+            max_downtime: None,
+            ..ReconnectPolicy::default()
+        }
+    }
+
+    #[test]
+    fn test_backoff_grows_and_caps() {
+        let mut backoff = Backoff::new(ReconnectPolicy {
+            max_delay: Duration::from_secs(5),
+            ..policy()
+        });
+
+        let delays: Vec<u64> = (0..5)
+            .map(|_| backoff.next_delay().unwrap().1.as_secs())
+            .collect();
+        assert_eq!(delays, vec![1, 2, 4, 5, 5]);
+    }
+
+    #[test]
+    fn test_backoff_max_attempts_and_reset() {
+        let mut backoff = Backoff::new(ReconnectPolicy {
+            max_attempts: Some(2),
+            ..policy()
+        });
+
+        assert_eq!(backoff.next_delay().unwrap().0, 1);
+        assert_eq!(backoff.next_delay().unwrap().0, 2);
+        assert!(backoff.next_delay().is_none());
+
+        backoff.reset();
+        assert_eq!(backoff.next_delay().unwrap().0, 1);
+    }
+
+    #[test]
+    fn test_backoff_keeps_counting_when_closed_right_after_connect() {
+        let mut backoff = Backoff::new(ReconnectPolicy {
+            max_attempts: Some(2),
+            stable_after: Duration::from_secs(60),
+            ..policy()
+        });
+
+        // 접속하자마자 끊기는 연결은 시도 횟수를 초기화하지 않습니다.
+        for attempt in 1..=2 {
+            backoff.connected();
+            backoff.disconnected();
+            assert_eq!(backoff.next_delay().unwrap().0, attempt);
+        }
+        backoff.connected();
+        backoff.disconnected();
+        assert!(backoff.next_delay().is_none());
+
+        // 충분히 유지된 연결이 끊기면 처음부터 다시 시도합니다.
+        let mut backoff = Backoff::new(ReconnectPolicy {
+            max_attempts: Some(2),
+            stable_after: Duration::ZERO,
+            ..policy()
+        });
+        backoff.next_delay();
+        backoff.next_delay();
+        backoff.connected();
+        backoff.disconnected();
+        assert_eq!(backoff.next_delay().unwrap().0, 1);
+    }
+
+    #[test]
+    fn test_backoff_disabled() {
+        let mut backoff = Backoff::new(ReconnectPolicy::disabled());
+        assert!(backoff.next_delay().is_none());
+    }
+}
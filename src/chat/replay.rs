@@ -0,0 +1,316 @@
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::Utc;
+use tokio::sync::{broadcast, mpsc, watch};
+use tokio::time::Instant;
+
+use crate::chat::bus::EventBus;
+use crate::chat::capture::CaptureReader;
+use crate::chat::commands::PendingCommands;
+use crate::chat::constants::message_codes::MessageCode;
+use crate::chat::decoder::{DecoderRegistry, PacketDecoder};
+use crate::chat::events::{
+    ChatEvent, ConnectedEvent, DisconnectedEvent, DonationEvent, ErrorEvent, Event, EventMeta,
+    EventStamp,
+};
+use crate::chat::filter::{EventFilter, EventStats, EventStream};
+use crate::chat::formatter::ChatFormatter;
+use crate::chat::message::MessageHandler;
+use crate::chat::options::SoopEventOptions;
+use crate::error::{Error, Result};
+use crate::models::LiveDetail;
+
+/// 녹화 파일을 재생하는 속도
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ReplaySpeed {
+    /// 녹화한 시간 간격 그대로 재생합니다.
+    #[default]
+    RealTime,
+    /// 시간 간격을 주어진 배율로 줄여 재생합니다. (2.0이면 2배속)
+    Accelerated(f64),
+    /// 기다리지 않고 최대한 빠르게 재생합니다.
+    Unlimited,
+}
+
+type BoxedCapture = CaptureReader<Box<dyn Read + Send>>;
+
+/// 녹화 파일(`capture` 모듈)을 실제 연결과 같은 파싱 과정으로 재생합니다.
+///
+/// 네트워크 없이 `SoopChatConnection`과 같은 이벤트를 받을 수 있어,
+/// 실제 트래픽으로 회귀 테스트를 작성하거나 사용자가 보고한 파싱 문제를 재현할 때 사용합니다.
+/// 재생을 시작하면 `Connected`, 녹화된 패킷의 이벤트, `Disconnected` 순서로 방송합니다.
+pub struct ReplayConnection {
+    capture: Mutex<Option<BoxedCapture>>,
+    speed: ReplaySpeed,
+    bus: EventBus,
+    decoders: DecoderRegistry,
+    stop_tx: watch::Sender<bool>,
+}
+
+impl ReplayConnection {
+    /// 녹화 파일을 열어 재생을 준비합니다.
+    pub fn open(path: impl AsRef<Path>, speed: ReplaySpeed) -> Result<Self> {
+        let file = BufReader::new(File::open(path)?);
+        Self::from_reader(file, speed)
+    }
+
+    /// 녹화 데이터를 읽을 reader로 재생을 준비합니다.
+    pub fn from_reader(reader: impl Read + Send + 'static, speed: ReplaySpeed) -> Result<Self> {
+        let capture = CaptureReader::new(Box::new(reader) as Box<dyn Read + Send>)?;
+        let (stop_tx, _) = watch::channel(false);
+
+        Ok(Self {
+            capture: Mutex::new(Some(capture)),
+            speed,
+            bus: EventBus::new(&SoopEventOptions::default()),
+            decoders: DecoderRegistry::default(),
+            stop_tx,
+        })
+    }
+
+    /// 재생을 시작합니다. 한 번만 재생할 수 있습니다.
+    pub fn start(&self) -> Result<()> {
+        let capture = self
+            .capture
+            .lock()
+            .unwrap()
+            .take()
+            .ok_or(Error::AlreadyStarted)?;
+
+        tokio::spawn(run_replay(
+            capture,
+            self.speed,
+            self.bus.clone(),
+            self.decoders.clone(),
+            self.stop_tx.subscribe(),
+        ));
+        Ok(())
+    }
+
+    /// 재생을 멈춥니다. 남은 패킷은 재생하지 않고 `Disconnected`를 보냅니다.
+    pub fn stop(&self) {
+        self.stop_tx.send_replace(true);
+    }
+
+    /// 메시지 코드에 사용자 정의 해석기를 등록합니다. (`SoopChatConnection::register_decoder` 참고)
+    pub fn register_decoder(&self, decoder: impl PacketDecoder + 'static) -> Result<()> {
+        self.decoders.register(Arc::new(decoder))
+    }
+
+    /// 등록한 해석기를 제거합니다. 제거했다면 true를 반환합니다.
+    pub fn unregister_decoder(&self, code: MessageCode) -> bool {
+        self.decoders.unregister(code)
+    }
+
+    /// 재생되는 이벤트를 수신할 "수신기"를 얻습니다. `Raw` 이벤트는 포함되지 않습니다.
+    pub fn subscribe(&self) -> broadcast::Receiver<Event> {
+        self.bus.subscribe()
+    }
+
+    /// 진단 이벤트(`Error`, `ParseError`, `Unknown`)만 수신하는 "수신기"를 얻습니다.
+    pub fn subscribe_diagnostics(&self) -> broadcast::Receiver<Event> {
+        self.bus.subscribe_diagnostics()
+    }
+
+    /// 재생되는 원본 패킷(`Event::Raw`)을 수신하는 "수신기"를 얻습니다. 재생이 끝나면 `Disconnected`가 전달됩니다.
+    pub fn subscribe_raw(&self) -> broadcast::Receiver<Event> {
+        self.bus.subscribe_raw()
+    }
+
+    /// 필터를 통과한 이벤트만 받는 스트림을 얻습니다.
+    pub fn subscribe_filtered(&self, filter: EventFilter) -> EventStream<Event> {
+        self.bus.subscribe_filtered(filter, None, Some)
+    }
+
+    /// 이벤트를 버리지 않는 필터 구독 스트림을 얻습니다. (`SoopChatConnection::subscribe_lossless` 참고)
+    pub fn subscribe_lossless(
+        &self,
+        filter: EventFilter,
+        max_block: Duration,
+    ) -> EventStream<Event> {
+        self.bus.subscribe_filtered(filter, Some(max_block), Some)
+    }
+
+    /// 지금까지 재생한 이벤트 수와, 구독자가 밀려 버려진 이벤트 수
+    pub fn event_stats(&self) -> EventStats {
+        self.bus.stats()
+    }
+
+    /// 후원 이벤트만 받는 스트림을 얻습니다.
+    pub fn subscribe_donations(&self) -> EventStream<DonationEvent> {
+        self.bus.subscribe_donations()
+    }
+
+    /// 채팅 이벤트만 받는 스트림을 얻습니다.
+    pub fn subscribe_chats(&self) -> EventStream<ChatEvent> {
+        self.bus.subscribe_chats()
+    }
+}
+
+async fn run_replay(
+    mut capture: BoxedCapture,
+    speed: ReplaySpeed,
+    bus: EventBus,
+    decoders: DecoderRegistry,
+    mut stop_rx: watch::Receiver<bool>,
+) {
+    let stamp = EventStamp::new(capture.streamer_id(), capture.chat_no());
+    let formatter = ChatFormatter::new(replay_live_detail(capture.chat_no()), String::new(), None);
+    // 재생 중에는 서버로 응답하거나 방송 정보를 다시 조회하지 않으므로, 수신 측은 바로 닫습니다.
+    // 방송 상태 변경은 비교 없이 그대로 방송됩니다.
+    let handler = MessageHandler::new(
+        &formatter,
+        bus.clone(),
+        mpsc::channel(1).0,
+        mpsc::unbounded_channel().0,
+        stamp.clone(),
+        decoders,
+        PendingCommands::default(),
+    );
+    let emit = |event: Event| {
+        bus.send(stamp.stamp(event)).ok();
+    };
+
+    emit(Event::Connected(ConnectedEvent {
+        meta: EventMeta::new(Utc::now()),
+    }));
+
+    let started = Instant::now();
+    let mut first_frame_time = None;
+    loop {
+        if *stop_rx.borrow() {
+            break;
+        }
+
+        let frame = match capture.next_frame() {
+            Ok(Some(frame)) => frame,
+            Ok(None) => break,
+            Err(e) => {
+                emit(Event::Error(ErrorEvent {
+                    meta: EventMeta::new(Utc::now()),
+                    code: None,
+                    reason: e.to_string(),
+                    raw: Arc::from(&[][..]),
+                }));
+                break;
+            }
+        };
+
+        // 첫 패킷을 기준으로 녹화된 시간 간격만큼 기다립니다.
+        let first = *first_frame_time.get_or_insert(frame.received_time);
+        let elapsed = (frame.received_time - first).to_std().unwrap_or_default();
+        let delay = match speed {
+            ReplaySpeed::RealTime => Some(elapsed),
+            ReplaySpeed::Accelerated(rate) if rate > 0.0 => {
+                Some(Duration::from_secs_f64(elapsed.as_secs_f64() / rate))
+            }
+            ReplaySpeed::Accelerated(_) | ReplaySpeed::Unlimited => None,
+        };
+        match delay {
+            Some(delay) => {
+                tokio::select! {
+                    _ = tokio::time::sleep_until(started + delay) => {}
+                    _ = stop_rx.changed() => break,
+                }
+            }
+            // 구독자가 이벤트를 처리할 수 있도록 양보합니다.
+            None => tokio::task::yield_now().await,
+        }
+
+        handler.handle_at(frame.data, frame.received_time).ok();
+        bus.flush().await;
+    }
+
+    emit(Event::Disconnected(DisconnectedEvent {
+        meta: EventMeta::new(Utc::now()),
+    }));
+    bus.close().await;
+}
+
+/// 재생용 포매터에 넘길 방송 정보, 응답 패킷을 보내지 않으므로 채팅방 번호만 채웁니다.
+fn replay_live_detail(chat_no: &str) -> LiveDetail {
+    LiveDetail {
+        is_live: true,
+        ch_domain: String::new(),
+        ch_pt: 0,
+        ch_no: chat_no.to_string(),
+        streamer_nick: String::new(),
+        title: String::new(),
+        categories: Vec::new(),
+        is_password: false,
+        is_adult: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chat::capture::CaptureWriter;
+    use crate::chat::commands::MessageType;
+    use crate::chat::constants::message_codes;
+    use crate::chat::events::EventKind;
+    use crate::chat::formatter::bundle;
+    use chrono::DateTime;
+
+    #[tokio::test]
+    async fn test_replay_emits_events_in_order() {
+        let time = DateTime::from_timestamp_micros(1_700_000_000_000_000).unwrap();
+        let mut writer = CaptureWriter::new(Vec::new(), "streamer", "42").unwrap();
+        writer
+            .write_frame(time, &bundle(MessageType::Chat, b"\x0chello\x0c"))
+            .unwrap();
+        writer.write_frame(time, b"garbage").unwrap();
+        let capture = writer.into_inner().unwrap();
+
+        let replay =
+            ReplayConnection::from_reader(std::io::Cursor::new(capture), ReplaySpeed::Unlimited)
+                .unwrap();
+        let mut events = replay.subscribe();
+        let mut raw = replay.subscribe_raw();
+        let mut errors = replay.subscribe_filtered(EventFilter::new().kind(EventKind::ParseError));
+        replay.start().unwrap();
+        assert!(matches!(replay.start(), Err(Error::AlreadyStarted)));
+
+        let mut received = Vec::new();
+        loop {
+            let event = events.recv().await.unwrap();
+            let done = matches!(event, Event::Disconnected(_));
+            received.push(event);
+            if done {
+                break;
+            }
+        }
+
+        assert!(matches!(received[0], Event::Connected(_)));
+        match &received[1] {
+            Event::ParseError(e) => {
+                assert_eq!(e.code, message_codes::CHAT);
+                assert_eq!(e.meta.streamer_id, "streamer");
+                assert_eq!(e.meta.chat_no, "42");
+                assert_eq!(e.meta.received_time, time);
+            }
+            e => panic!("unexpected event: {e:?}"),
+        }
+        assert!(matches!(received[2], Event::Error(_)));
+        assert!(matches!(received[3], Event::Disconnected(_)));
+        let seqs: Vec<u64> = received.iter().map(|e| e.meta().seq).collect();
+        assert_eq!(seqs, vec![1, 3, 5, 6]);
+
+        // Raw 이벤트는 Raw 구독자에게만 전달됩니다.
+        assert!(
+            matches!(raw.try_recv(), Ok(Event::Raw(e)) if e.meta.seq == 2 && e.meta.received_time == time)
+        );
+        assert!(matches!(raw.try_recv(), Ok(Event::Raw(e)) if e.meta.seq == 4));
+
+        // 필터 구독은 해당 이벤트만 받고, 재생이 끝나면 스트림이 끝납니다.
+        assert!(matches!(
+            errors.recv().await,
+            Some(Ok(Event::ParseError(_)))
+        ));
+        assert!(errors.recv().await.is_none());
+    }
+}
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+use crate::chat::parser::constants::{SUPER_USERS, freeze_target_flags};
+use crate::models::LiveDetail;
+
 #[derive(Debug, Serialize, Clone, Deserialize)]
 pub struct UserSubscribe {
     pub acc: u32,
@@ -73,3 +76,153 @@ pub enum MissionParser {
     BattleNotice,
     ChallengeNotice,
 }
+
+/// 방송 상태 변경(BJStateChange)에서 비교하는 방송 정보
+#[derive(Debug, Serialize, Clone, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BroadcastInfo {
+    pub title: String,
+    pub categories: Vec<String>,
+    pub is_password: bool,
+    pub is_adult: bool,
+}
+
+impl From<&LiveDetail> for BroadcastInfo {
+    fn from(live_detail: &LiveDetail) -> Self {
+        Self {
+            title: live_detail.title.clone(),
+            categories: live_detail.categories.clone(),
+            is_password: live_detail.is_password,
+            is_adult: live_detail.is_adult,
+        }
+    }
+}
+
+impl BroadcastInfo {
+    /// 두 방송 정보를 비교하여 바뀐 항목을 반환합니다.
+    pub fn diff(&self, after: &BroadcastInfo) -> Vec<BroadcastChange> {
+        let mut changes = Vec::new();
+        if self.title != after.title {
+            changes.push(BroadcastChange::Title);
+        }
+        if self.categories != after.categories {
+            changes.push(BroadcastChange::Category);
+        }
+        if self.is_password != after.is_password {
+            changes.push(BroadcastChange::Password);
+        }
+        if self.is_adult != after.is_adult {
+            changes.push(BroadcastChange::Adult);
+        }
+        changes
+    }
+}
+
+/// 방송 상태 변경 항목
+#[derive(Debug, Serialize, Clone, Copy, Deserialize, PartialEq, Eq)]
+pub enum BroadcastChange {
+    Title,
+    Category,
+    Password,
+    Adult,
+    /// 방송 종료
+    Ended,
+}
+
+/// 채팅방 설정 종류
+#[derive(Debug, Serialize, Clone, Copy, Deserialize, PartialEq, Eq)]
+pub enum ChatRoomSetting {
+    /// 채팅 금지 모드
+    ChatBlock,
+    /// 얼리기 모드
+    Ice,
+}
+
+/// 채팅 금지 등 관리 명령을 내리는 권한
+#[derive(Debug, Serialize, Clone, Copy, Deserialize, PartialEq, Eq)]
+pub enum SuperUser {
+    Streamer,
+    Manager,
+    Operator,
+    Cleaner,
+}
+
+impl SuperUser {
+    /// 패킷에 실리는 권한 번호
+    pub(crate) fn code(self) -> usize {
+        match self {
+            SuperUser::Streamer => 0,
+            SuperUser::Manager => 1,
+            SuperUser::Operator => 2,
+            SuperUser::Cleaner => 4,
+        }
+    }
+
+    /// `MuteEvent::superuser_type`에 사용되는 이름
+    pub fn as_str(self) -> &'static str {
+        SUPER_USERS[self.code()]
+    }
+}
+
+/// 얼린 채팅방에서 채팅을 허용할 대상
+#[derive(Debug, Serialize, Clone, Copy, Deserialize, PartialEq, Eq)]
+pub enum FreezeTarget {
+    Normal,
+    Fan,
+    Supporter,
+    TopFan,
+    Follower,
+    Manager,
+}
+
+impl FreezeTarget {
+    /// 패킷에 실리는 대상 플래그
+    pub(crate) fn flag(self) -> u32 {
+        match self {
+            FreezeTarget::Normal => freeze_target_flags::NORMAL,
+            FreezeTarget::Fan => freeze_target_flags::FAN,
+            FreezeTarget::Supporter => freeze_target_flags::SUPPORTER,
+            FreezeTarget::TopFan => freeze_target_flags::TOP_FAN,
+            FreezeTarget::Follower => freeze_target_flags::FOLLOWER,
+            FreezeTarget::Manager => freeze_target_flags::MANAGER,
+        }
+    }
+
+    /// `FreezeEvent::targets`에 사용되는 이름
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FreezeTarget::Normal => "BJ",
+            FreezeTarget::Fan => "FAN",
+            FreezeTarget::Supporter => "SUPPORTER",
+            FreezeTarget::TopFan => "TOP_FAN",
+            FreezeTarget::Follower => "FOLLOWER",
+            FreezeTarget::Manager => "MANAGER",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_broadcast_info_diff() {
+        let before = BroadcastInfo {
+            title: "방송".to_string(),
+            categories: vec!["게임".to_string()],
+            is_password: false,
+            is_adult: false,
+        };
+        let after = BroadcastInfo {
+            title: "새 방송".to_string(),
+            is_adult: true,
+            ..before.clone()
+        };
+
+        assert_eq!(
+            before.diff(&after),
+            vec![BroadcastChange::Title, BroadcastChange::Adult]
+        );
+        assert!(before.diff(&before).is_empty());
+    }
+}
@@ -1,12 +1,105 @@
+use std::sync::Arc;
+
 use rustls::{
-    SignatureScheme,
-    client::danger::{ServerCertVerified, ServerCertVerifier},
+    CertificateError, ClientConfig, RootCertStore, SignatureScheme,
+    client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+    crypto::{CryptoProvider, WebPkiSupportedAlgorithms},
     pki_types::{CertificateDer, ServerName, UnixTime},
 };
 
+use crate::{
+    Result,
+    chat::options::{SoopTlsOptions, TlsVerification},
+};
+
+/// TLS 옵션으로 WebSocket 연결에 사용할 rustls 설정을 만듭니다.
+pub(crate) fn build_client_config(options: &SoopTlsOptions) -> Result<ClientConfig> {
+    // 전역 CryptoProvider를 설치하지 않고, 이 연결에서만 사용할 provider를 고릅니다.
+    let provider = options
+        .crypto_provider
+        .clone()
+        .or_else(|| CryptoProvider::get_default().cloned())
+        .unwrap_or_else(|| Arc::new(rustls::crypto::ring::default_provider()));
+
+    let builder = ClientConfig::builder_with_provider(Arc::clone(&provider))
+        .with_safe_default_protocol_versions()?;
+
+    let config = match &options.verification {
+        TlsVerification::WebPki => builder.with_root_certificates(RootCertStore {
+            roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+        }),
+        TlsVerification::CustomRoots(roots) => builder.with_root_certificates(Arc::clone(roots)),
+        TlsVerification::Pinned(cert) => {
+            builder
+                .dangerous()
+                .with_custom_certificate_verifier(Arc::new(PinnedVerification {
+                    cert: cert.clone(),
+                    algorithms: provider.signature_verification_algorithms,
+                }))
+        }
+        #[cfg(feature = "dangerous-insecure-tls")]
+        TlsVerification::Insecure => builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(NoVerification)),
+    };
+
+    Ok(config.with_no_client_auth())
+}
+
+/// 서버 인증서가 고정된 인증서와 같은지만 확인하는 검증기입니다.
+/// 핸드셰이크 서명은 provider의 알고리즘으로 정상 검증합니다.
+#[derive(Debug)]
+struct PinnedVerification {
+    cert: CertificateDer<'static>,
+    algorithms: WebPkiSupportedAlgorithms,
+}
+
+impl ServerCertVerifier for PinnedVerification {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+        if end_entity.as_ref() == self.cert.as_ref() {
+            Ok(ServerCertVerified::assertion())
+        } else {
+            Err(rustls::Error::InvalidCertificate(
+                CertificateError::ApplicationVerificationFailure,
+            ))
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.algorithms.supported_schemes()
+    }
+}
+
+#[cfg(feature = "dangerous-insecure-tls")]
 #[derive(Debug)]
 pub struct NoVerification;
 
+#[cfg(feature = "dangerous-insecure-tls")]
 impl ServerCertVerifier for NoVerification {
     fn verify_server_cert(
         &self,
@@ -15,7 +108,7 @@ impl ServerCertVerifier for NoVerification {
         _server_name: &ServerName<'_>,
         _ocsp_response: &[u8],
         _now: UnixTime,
-    ) -> Result<ServerCertVerified, rustls::Error> {
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
         Ok(ServerCertVerified::assertion())
     }
 
@@ -24,8 +117,8 @@ impl ServerCertVerifier for NoVerification {
         _message: &[u8],
         _cert: &CertificateDer<'_>,
         _dss: &rustls::DigitallySignedStruct,
-    ) -> Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error> {
-        Ok(rustls::client::danger::HandshakeSignatureValid::assertion())
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        Ok(HandshakeSignatureValid::assertion())
     }
 
     fn verify_tls13_signature(
@@ -33,8 +126,8 @@ impl ServerCertVerifier for NoVerification {
         _message: &[u8],
         _cert: &CertificateDer<'_>,
         _dss: &rustls::DigitallySignedStruct,
-    ) -> Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error> {
-        Ok(rustls::client::danger::HandshakeSignatureValid::assertion())
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        Ok(HandshakeSignatureValid::assertion())
     }
 
     fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
@@ -51,3 +144,35 @@ impl ServerCertVerifier for NoVerification {
         ]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pinned_verification_rejects_other_certificate() {
+        let verifier = PinnedVerification {
+            cert: CertificateDer::from(vec![1, 2, 3]),
+            algorithms: rustls::crypto::ring::default_provider().signature_verification_algorithms,
+        };
+        let server_name = ServerName::try_from("chat.sooplive.co.kr").unwrap();
+
+        let verify = |cert: Vec<u8>| {
+            verifier.verify_server_cert(
+                &CertificateDer::from(cert),
+                &[],
+                &server_name,
+                &[],
+                UnixTime::now(),
+            )
+        };
+
+        assert!(verify(vec![1, 2, 3]).is_ok());
+        assert!(verify(vec![3, 2, 1]).is_err());
+    }
+
+    #[test]
+    fn test_default_options_build_config() {
+        assert!(build_client_config(&SoopTlsOptions::default()).is_ok());
+    }
+}
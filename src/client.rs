@@ -1,18 +1,257 @@
-use crate::chat::events::Event;
-use crate::constants::{EMOTICON_API_URL, PLAYER_LIVE_API_URL};
+use crate::constants::{
+    AUTH_TICKET_COOKIE, CHANNEL_API_BASE_URL, DEFAULT_USER_AGENT, EMOTICON_API_PATH,
+    LIVE_API_BASE_URL, LOGIN_API_BASE_URL, LOGIN_API_PATH, MOBILE_API_BASE_URL,
+    PLAYER_LIVE_API_PATH, VOD_DETAIL_API_PATH,
+};
 use crate::error::{Error, Result};
 use crate::models::{
-    LiveDetail, LiveDetailToCheck, RawLiveDetail, RawStation, RawVODDetailResponse, RawVODResponse,
-    SignatureEmoticonData, SignatureEmoticonResponse, Station, VOD, VODDetail, VODFile,
-    parse_soop_timestamp,
+    AuthTicket, LiveDetail, LiveDetailToCheck, RawLiveDetail, RawLoginResponse, RawStation,
+    RawVODDetailResponse, RawVODResponse, SignatureEmoticonData, SignatureEmoticonResponse,
+    Station, VOD, VODDetail, VODFile, parse_soop_timestamp,
+};
+use crate::vod_chat::{
+    VODChat, VODChatChunk, VODChatFailure, VODChatItem, VODChatOptions, VODChatProgress,
+    VODChatRange, VODChatResumeToken, file_clocks, plan_chunks,
+};
+use crate::vod_chat_parser::{
+    ParsedVODChat, VODChatParseMode, VodClock, parse_vod_chat_xml_with_clock,
 };
-use crate::vod_chat_parser::parse_vod_chat_xml_with_start_time;
-use futures_util::TryFutureExt;
-use reqwest::{Client, Response};
+use futures_util::stream::{self, Stream, StreamExt};
+use reqwest::cookie::{CookieStore, Jar};
+use reqwest::header::{HeaderValue, USER_AGENT};
+use reqwest::{Client, Proxy, RequestBuilder, Response};
+use std::pin::pin;
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
+
+/// SOOP API 서버 주소 모음입니다.
+/// 테스트용 mock 서버나 프록시(relay)를 사용할 때 기본값 대신 지정합니다.
+#[derive(Clone, Debug)]
+pub struct SoopApiBaseUrls {
+    /// 방송 정보, 시그니처 이모티콘 API (live.sooplive.co.kr)
+    pub live: String,
+    /// 방송국, VOD 목록 API (chapi.sooplive.co.kr)
+    pub channel: String,
+    /// VOD 상세 정보 API (api.m.sooplive.co.kr)
+    pub mobile: String,
+    /// 로그인 API (login.sooplive.co.kr)
+    pub login: String,
+}
+
+impl Default for SoopApiBaseUrls {
+    fn default() -> Self {
+        Self {
+            live: LIVE_API_BASE_URL.to_string(),
+            channel: CHANNEL_API_BASE_URL.to_string(),
+            mobile: MOBILE_API_BASE_URL.to_string(),
+            login: LOGIN_API_BASE_URL.to_string(),
+        }
+    }
+}
+
+/// 기본 주소 뒤에 경로를 붙입니다. 기본 주소 끝의 '/'는 무시합니다.
+fn join_url(base: &str, path: &str) -> String {
+    format!("{}{}", base.trim_end_matches('/'), path)
+}
+
+/// 실패한 HTTP 요청을 다시 시도하는 정책입니다.
+///
+/// 5xx 응답과 연결 실패, 타임아웃 같은 일시적인 오류만 재시도하며,
+/// 대기 시간은 시도마다 두 배씩 늘어납니다.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// 첫 요청 이후 최대 재시도 횟수
+    pub max_retries: u32,
+    /// 첫 번째 재시도 전 대기 시간
+    pub initial_backoff: Duration,
+    /// 대기 시간의 상한
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// 재시도를 하지 않는 정책
+    pub fn disabled() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    /// n번째(1부터 시작) 재시도 전 대기 시간을 계산합니다.
+    fn backoff(&self, retry: u32) -> Duration {
+        let exp = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(exp)
+            .min(self.max_backoff)
+    }
+}
+
+/// 재시도 정책에 따라 다시 보낼 결과인지 확인합니다. 5xx 응답과 연결 실패, 타임아웃만 해당합니다.
+fn is_retryable(result: &reqwest::Result<Response>) -> bool {
+    match result {
+        Ok(response) => response.status().is_server_error(),
+        Err(e) => e.is_timeout() || e.is_connect(),
+    }
+}
+
+/// `SoopHttpClient`를 구성하는 빌더입니다.
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use soup_sdk::SoopHttpClient;
+///
+/// let client = SoopHttpClient::builder()
+///     .connect_timeout(Duration::from_secs(5))
+///     .read_timeout(Duration::from_secs(10))
+///     .user_agent("my-bot/1.0")
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug)]
+pub struct SoopHttpClientBuilder {
+    base_urls: SoopApiBaseUrls,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    proxy: Option<Proxy>,
+    user_agent: String,
+    client: Option<Client>,
+    retry: RetryPolicy,
+}
+
+impl Default for SoopHttpClientBuilder {
+    fn default() -> Self {
+        Self {
+            base_urls: SoopApiBaseUrls::default(),
+            connect_timeout: Some(Duration::from_secs(10)),
+            read_timeout: Some(Duration::from_secs(30)),
+            timeout: None,
+            proxy: None,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            client: None,
+            retry: RetryPolicy::default(),
+        }
+    }
+}
+
+impl SoopHttpClientBuilder {
+    /// 요청을 보낼 API 서버 주소
+    pub fn base_urls(mut self, base_urls: SoopApiBaseUrls) -> Self {
+        self.base_urls = base_urls;
+        self
+    }
+
+    /// 서버와 연결을 맺기까지의 제한 시간 (기본 10초)
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// 응답 데이터를 읽는 동안 데이터가 오지 않을 때의 제한 시간 (기본 30초)
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// 요청 하나의 전체 제한 시간 (기본 없음)
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// HTTP/HTTPS 프록시를 사용합니다.
+    /// SOCKS 프록시는 reqwest의 `socks` feature가 활성화되어 있어야 합니다.
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// 모든 요청에 사용할 User-Agent
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// 직접 구성한 reqwest 클라이언트를 사용합니다.
+    /// 이 경우 타임아웃과 프록시 설정은 주입한 클라이언트의 설정을 따릅니다.
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// 실패한 요청의 재시도 정책
+    pub fn retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn build(self) -> Result<SoopHttpClient> {
+        let user_agent = HeaderValue::from_str(&self.user_agent)
+            .map_err(|e| Error::InvalidConfig(format!("User-Agent: {}", e)))?;
+
+        let cookie_jar = Arc::new(Jar::default());
+        let client = match self.client {
+            Some(client) => client,
+            None => {
+                let mut builder = Client::builder()
+                    .cookie_provider(Arc::clone(&cookie_jar))
+                    .user_agent(user_agent.clone());
+
+                if let Some(timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(timeout);
+                }
+                if let Some(timeout) = self.read_timeout {
+                    builder = builder.read_timeout(timeout);
+                }
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if let Some(proxy) = self.proxy {
+                    builder = builder.proxy(proxy);
+                }
+
+                builder.build()?
+            }
+        };
+
+        Ok(SoopHttpClient {
+            client,
+            cookie_jar,
+            base_urls: self.base_urls,
+            user_agent,
+            retry: self.retry,
+        })
+    }
+}
 
 #[derive(Debug)]
 pub struct SoopHttpClient {
     client: Client,
+    // 로그인 세션 쿠키를 보관하는 저장소
+    cookie_jar: Arc<Jar>,
+    // 요청을 보낼 API 서버 주소
+    base_urls: SoopApiBaseUrls,
+    // 모든 요청에 붙는 User-Agent
+    user_agent: HeaderValue,
+    // 실패한 요청의 재시도 정책
+    retry: RetryPolicy,
+}
+
+impl Default for SoopHttpClient {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// (is_live_detail, live_detail)
@@ -20,20 +259,128 @@ type LiveDetailState = (bool, Option<LiveDetail>);
 
 impl SoopHttpClient {
     pub fn new() -> Self {
-        Self {
-            client: Client::new(),
+        Self::builder()
+            .build()
+            .expect("Failed to build HTTP client")
+    }
+
+    pub fn builder() -> SoopHttpClientBuilder {
+        SoopHttpClientBuilder::default()
+    }
+
+    /// 기본 SOOP 서버 대신 지정한 주소로 요청을 보내는 클라이언트를 만듭니다.
+    pub fn with_base_urls(base_urls: SoopApiBaseUrls) -> Self {
+        Self::builder()
+            .base_urls(base_urls)
+            .build()
+            .expect("Failed to build HTTP client")
+    }
+
+    /// 현재 사용 중인 API 서버 주소
+    pub fn base_urls(&self) -> &SoopApiBaseUrls {
+        &self.base_urls
+    }
+
+    /// 요청을 전송하고, 성공 응답만 반환합니다.
+    /// 5xx 응답과 일시적인 오류는 재시도 정책에 따라 다시 시도합니다.
+    async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let request = request.header(USER_AGENT, self.user_agent.clone());
+        let mut retry = 0;
+
+        loop {
+            // 본문이 스트림이라 복제할 수 없는 요청은 한 번만 보냅니다.
+            let Some(attempt) = request.try_clone() else {
+                return Ok(request.send().await?.error_for_status()?);
+            };
+
+            let result = attempt.send().await;
+            if !is_retryable(&result) || retry >= self.retry.max_retries {
+                return Ok(result?.error_for_status()?);
+            }
+
+            retry += 1;
+            tokio::time::sleep(self.retry.backoff(retry)).await;
         }
     }
 
+    /// 요청을 재시도 없이 한 번만 전송하고, 성공 응답만 반환합니다.
+    /// 다시 보내면 안 되는 요청(로그인 등)에 사용합니다.
+    async fn send_once(&self, request: RequestBuilder) -> Result<Response> {
+        let request = request.header(USER_AGENT, self.user_agent.clone());
+        Ok(request.send().await?.error_for_status()?)
+    }
+
+    /// SOOP 계정으로 로그인하고, 채팅 서버 인증에 사용할 티켓을 가져옵니다.
+    /// 로그인 쿠키는 쿠키 저장소에 보관되어 이후 요청에도 함께 전송됩니다.
+    ///
+    /// 반복된 로그인 시도로 계정이 잠기지 않도록, 재시도 정책과 관계없이 한 번만 요청합니다.
+    pub async fn login(&self, user_id: &str, password: &str) -> Result<AuthTicket> {
+        let params = [
+            ("szWork", "login"),
+            ("szType", "json"),
+            ("szUid", user_id),
+            ("szPassword", password),
+            ("isSaveId", "true"),
+            ("isSavePw", "false"),
+            ("isSaveJoin", "false"),
+            ("isLoginRetain", "Y"),
+        ];
+
+        let request = self
+            .client
+            .post(join_url(&self.base_urls.login, LOGIN_API_PATH))
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .form(&params);
+
+        let response = self.send_once(request).await?;
+
+        // 주입된 클라이언트는 쿠키 저장소를 쓰지 않을 수 있으므로, 응답 쿠키를 먼저 확인합니다.
+        let response_ticket = response
+            .cookies()
+            .find(|cookie| cookie.name() == AUTH_TICKET_COOKIE)
+            .map(|cookie| cookie.value().to_string());
+
+        let login_response = response.json::<RawLoginResponse>().await?;
+
+        if login_response.result != 1 {
+            return Err(Error::LoginFailed(format!(
+                "결과 코드 {}",
+                login_response.result
+            )));
+        }
+
+        let ticket = response_ticket
+            .or_else(|| self.find_cookie(AUTH_TICKET_COOKIE))
+            .ok_or_else(|| Error::LoginFailed("인증 티켓을 찾을 수 없습니다.".to_string()))?;
+
+        Ok(AuthTicket {
+            user_id: user_id.to_string(),
+            ticket,
+        })
+    }
+
+    /// 쿠키 저장소에서 이름에 해당하는 쿠키 값을 찾습니다.
+    fn find_cookie(&self, name: &str) -> Option<String> {
+        // 로그인 서버가 발급한 쿠키이므로 로그인 서버 주소 기준으로 찾습니다.
+        let url = Url::parse(&self.base_urls.login).ok()?;
+        let header = self.cookie_jar.cookies(&url)?;
+
+        header.to_str().ok()?.split("; ").find_map(|pair| {
+            pair.split_once('=')
+                .filter(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        })
+    }
+
     /// 스트리머 ID로 방송 상세 정보를 가져옵니다.
     pub async fn get_live_detail_state(&self, streamer_id: &str) -> Result<LiveDetailState> {
         let resp = self.fetch_live_detail_response(streamer_id).await?;
 
-        let bytes = resp.bytes().await.map_err(|e| Error::ResponseJson(e))?;
+        let bytes = resp.bytes().await.map_err(Error::ResponseJson)?;
 
         // bytes를 공유해서 두 번의 파싱을 수행하되, bytes 복사는 피합니다
         let live_detail_to_check =
-            serde_json::from_slice::<LiveDetailToCheck>(&bytes).map_err(|e| Error::SerdeJson(e))?;
+            serde_json::from_slice::<LiveDetailToCheck>(&bytes).map_err(Error::SerdeJson)?;
 
         if !live_detail_to_check.is_streaming() {
             return Ok((false, None));
@@ -41,9 +388,9 @@ impl SoopHttpClient {
 
         // 방송 중인 경우에만 전체 JSON을 파싱합니다
         let live_detail =
-            serde_json::from_slice::<RawLiveDetail>(&bytes).map_err(|e| Error::SerdeJson(e))?;
+            serde_json::from_slice::<RawLiveDetail>(&bytes).map_err(Error::SerdeJson)?;
 
-        return Ok((
+        Ok((
             true,
             Some(LiveDetail {
                 is_live: live_detail_to_check.is_streaming(),
@@ -53,33 +400,28 @@ impl SoopHttpClient {
                 streamer_nick: live_detail.channel.bj_nick,
                 title: live_detail.channel.title,
                 categories: live_detail.channel.categories,
+                is_password: live_detail.channel.bpwd == "Y",
+                is_adult: live_detail.channel.grade >= 19,
             }),
-        ));
+        ))
     }
 
     pub async fn get_station(&self, streamer_id: &str) -> Result<Station> {
-        let request = self
-            .client
-            .get(format!(
-                "https://chapi.sooplive.co.kr/api/{}/station",
-                streamer_id
-            )) // URL 쿼리 파라미터 추가
-            .header("User-Agent", "Mozilla/5.0 (compatible; SoopClient/1.0)"); // User-Agent 헤더 설정
-
-        let response = request.send().await?;
+        let request = self.client.get(join_url(
+            &self.base_urls.channel,
+            &format!("/api/{}/station", streamer_id),
+        ));
 
-        if !response.status().is_success() {
-            return Err(Error::Request(response.error_for_status().unwrap_err()));
-        }
+        let response = self.send(request).await?;
 
         let raw = response.json::<RawStation>().await?;
 
-        return Ok(Station {
+        Ok(Station {
             broad_start: parse_soop_timestamp(&raw.station.broad_start),
             is_password: raw.broad.is_password,
             viewer_count: raw.broad.viewer_count,
             title: raw.broad.title,
-        });
+        })
     }
 
     pub async fn get_signature_emoticon(&self, streamer_id: &str) -> Result<SignatureEmoticonData> {
@@ -88,20 +430,15 @@ impl SoopHttpClient {
 
         let request = self
             .client
-            .post(EMOTICON_API_URL)
+            .post(join_url(&self.base_urls.live, EMOTICON_API_PATH))
             .header("Content-Type", "application/x-www-form-urlencoded") // 헤더 설정
-            .header("User-Agent", "Mozilla/5.0 (compatible; SoopClient/1.0)") // User-Agent 헤더 설정
             .form(&params); // form-urlencoded 본문 추가
 
-        let response = request.send().await?;
-
-        if !response.status().is_success() {
-            return Err(Error::Request(response.error_for_status().unwrap_err()));
-        }
+        let response = self.send(request).await?;
 
         let emoticon_response = response.json::<SignatureEmoticonResponse>().await?;
 
-        return Ok(emoticon_response.data);
+        Ok(emoticon_response.data)
     }
 
     /// 스트리머 ID로 방송 상세 정보 response를 가져옵니다.
@@ -111,37 +448,28 @@ impl SoopHttpClient {
 
         let request = self
             .client
-            .post(PLAYER_LIVE_API_URL)
+            .post(join_url(&self.base_urls.live, PLAYER_LIVE_API_PATH))
             .query(&[("bjid", streamer_id)]) // URL 쿼리 파라미터 추가
             .header("Content-Type", "application/x-www-form-urlencoded") // 헤더 설정
-            .header("User-Agent", "Mozilla/5.0 (compatible; SoopClient/1.0)") // User-Agent 헤더 설정
             .form(&params); // form-urlencoded 본문 추가
 
-        let response = request.send().await?;
-
-        if !response.status().is_success() {
-            return Err(Error::Request(response.error_for_status().unwrap_err()));
-        }
+        let response = self.send(request).await?;
 
         Ok(response)
     }
 
     pub async fn get_vod_list(&self, streamer_id: &str, page: u32) -> Result<Vec<VOD>> {
-        let url = format!(
-            "https://chapi.sooplive.co.kr/api/{}/vods/review?page={}&per_page=60&orderby=reg_date&field=title%2Ccontents&created=false",
-            streamer_id, page
+        let url = join_url(
+            &self.base_urls.channel,
+            &format!(
+                "/api/{}/vods/review?page={}&per_page=60&orderby=reg_date&field=title%2Ccontents&created=false",
+                streamer_id, page
+            ),
         );
 
-        let request = self
-            .client
-            .get(&url)
-            .header("User-Agent", "Mozilla/5.0 (compatible; SoopClient/1.0)");
-
-        let response = request.send().await?;
+        let request = self.client.get(&url);
 
-        if !response.status().is_success() {
-            return Err(Error::Request(response.error_for_status().unwrap_err()));
-        }
+        let response = self.send(request).await?;
 
         let vod_response = response.json::<RawVODResponse>().await?;
         Ok(vod_response.into_vods())
@@ -152,86 +480,532 @@ impl SoopHttpClient {
 
         let request = self
             .client
-            .post("https://api.m.sooplive.co.kr/station/video/a/view")
+            .post(join_url(&self.base_urls.mobile, VOD_DETAIL_API_PATH))
             .header("Content-Type", "application/x-www-form-urlencoded")
-            .header("User-Agent", "Mozilla/5.0 (compatible; SoopClient/1.0)")
             .form(&params);
 
-        let response = request.send().await?;
-
-        if !response.status().is_success() {
-            return Err(Error::Request(response.error_for_status().unwrap_err()));
-        }
+        let response = self.send(request).await?;
 
         let vod_detail_response = response.json::<RawVODDetailResponse>().await?;
         vod_detail_response.into_vod_detail()
     }
 
     pub async fn get_vod_chat(&self, chat_url: &str, start_time: u64) -> Result<String> {
-        let url = format!("{}&startTime={}", chat_url, start_time);
+        let response = self
+            .send(self.vod_chat_request(chat_url, start_time))
+            .await?;
 
-        let request = self
-            .client
+        let xml_content = response.text().await?;
+        Ok(xml_content)
+    }
+
+    fn vod_chat_request(&self, chat_url: &str, start_time: u64) -> RequestBuilder {
+        let url = format!("{}&startTime={}", chat_url, start_time);
+        self.client
             .get(&url)
-            .header("User-Agent", "Mozilla/5.0 (compatible; SoopClient/1.0)");
+            .header(USER_AGENT, self.user_agent.clone())
+    }
+
+    /// 구간 하나의 채팅을 가져와 파싱합니다.
+    /// 5xx 응답과 일시적인 오류는 `VODChatOptions::retry`에 따라 다시 시도하며,
+    /// 4xx 응답과 파싱 오류는 재시도하지 않습니다.
+    /// 요청이 겹쳐 재시도되지 않도록 클라이언트의 재시도 정책은 적용하지 않습니다.
+    async fn get_chunk_chat_events(
+        &self,
+        file: &VODFile,
+        clock: VodClock,
+        range: VODChatRange,
+        options: &ChunkOptions,
+    ) -> Result<ParsedVODChat> {
+        let mut attempt = 0;
+
+        let xml_content = loop {
+            let result = self.vod_chat_request(&file.chat, range.start).send().await;
+            if !is_retryable(&result) || attempt >= options.retry.max_retries {
+                break result?.error_for_status()?.text().await?;
+            }
+
+            attempt += 1;
+            tokio::time::sleep(options.retry.backoff(attempt)).await;
+        };
 
-        let response = request.send().await?;
+        parse_chunk_xml(&xml_content, clock, range, file, options.parse_mode)
+    }
 
-        if !response.status().is_success() {
-            return Err(Error::Request(response.error_for_status().unwrap_err()));
+    /// VOD 채팅을 구간별로 나눠 동시에 내려받습니다.
+    ///
+    /// 구간 결과는 요청 순서대로 `on_chunk`에 전달됩니다. 함께 전달되는 재개 토큰을 저장해 두면,
+    /// 중단된 뒤에 `VODChatOptions::resume`으로 지정해 이어서 내려받을 수 있습니다.
+    /// 모든 구간을 처리한 뒤의 재개 토큰을 반환합니다.
+    pub async fn download_vod_chat(
+        &self,
+        vod_id: u64,
+        options: &VODChatOptions,
+        mut on_chunk: impl FnMut(VODChatChunk),
+    ) -> Result<VODChatResumeToken> {
+        let mut token = resume_token(vod_id, options)?;
+        let vod_detail = Arc::new(self.get_vod_detail(vod_id).await?);
+
+        let mut chunks = pin!(self.chunk_stream(vod_detail, options, token.clone()));
+        while let Some(chunk) = chunks.next().await {
+            token = chunk.resume_token.clone();
+            on_chunk(chunk);
         }
 
-        let xml_content = response.text().await?;
-        Ok(xml_content)
+        Ok(token)
     }
 
-    async fn get_file_chat_events(
-        &self,
-        file: &VODFile,
-        broad_start: &str,
-        chunk_size_seconds: u64,
-    ) -> Result<Vec<Event>> {
-        let duration_seconds = file.duration / 1_000_000; // 마이크로초를 초로 변환
-        let mut all_events = Vec::new();
-        // ! 파일 시작점과 chunk size 계산 필요
-        let mut current_time = 0;
-
-        while current_time < duration_seconds {
-            match self.get_vod_chat(&file.chat, current_time).await {
-                Ok(xml_content) => {
-                    if !xml_content.trim().is_empty() {
-                        match parse_vod_chat_xml_with_start_time(&xml_content, Some(broad_start)) {
-                            Ok(mut events) => {
-                                all_events.append(&mut events);
-                            }
-                            Err(e) => {
-                                eprintln!("XML 파싱 오류 (시간 {}초): {}", current_time, e);
-                            }
+    /// VOD 채팅을 내려받으면서 이벤트를 하나씩 전달하는 스트림을 얻습니다.
+    ///
+    /// 전체 이벤트를 메모리에 모으지 않으므로 긴 VOD에 적합합니다.
+    /// 구간 안의 이벤트는 시간 순서로 정렬되며, 건너뛴 요소와 실패한 구간은
+    /// `VODChatItem::Warning`, `VODChatItem::ChunkFailed`로 알린 뒤 계속 진행합니다.
+    /// `Err`는 VOD 정보를 가져오지 못했거나 옵션이 잘못된 경우에만 전달되며, 그 뒤로 스트림이 끝납니다.
+    ///
+    /// 항목은 `Event`가 아닌 `VODChatItem`입니다. `Result<Event>`로 받으면 경고나 구간 실패를
+    /// 스트림을 끝내는 오류와 구분할 수 없어, 이벤트와 함께 같은 스트림으로 전달합니다.
+    /// 이벤트만 필요하다면 `VODChatItem::Event`만 골라 쓰면 됩니다.
+    ///
+    /// ```no_run
+    /// use futures_util::StreamExt;
+    /// use soup_sdk::{SoopHttpClient, VODChatItem};
+    ///
+    /// # async fn run() {
+    /// let client = SoopHttpClient::new();
+    /// let mut items = std::pin::pin!(client.stream_vod_chat(123456));
+    /// while let Some(item) = items.next().await {
+    ///     match item {
+    ///         Ok(VODChatItem::Event(event)) => println!("{:?}", event),
+    ///         Ok(item) => eprintln!("{:?}", item),
+    ///         Err(e) => eprintln!("{}", e),
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn stream_vod_chat(&self, vod_id: u64) -> impl Stream<Item = Result<VODChatItem>> + '_ {
+        self.stream_vod_chat_with(vod_id, VODChatOptions::default(), |_| {})
+    }
+
+    /// 옵션을 지정해 VOD 채팅 스트림을 얻습니다.
+    /// 구간 하나를 처리할 때마다, 그 구간의 이벤트를 전달하기 전에 `on_progress`를 호출합니다.
+    pub fn stream_vod_chat_with<'a, F>(
+        &'a self,
+        vod_id: u64,
+        options: VODChatOptions,
+        mut on_progress: F,
+    ) -> impl Stream<Item = Result<VODChatItem>> + use<'a, F>
+    where
+        F: FnMut(VODChatProgress) + 'a,
+    {
+        let chunks = stream::once(async move {
+            let token = resume_token(vod_id, &options)?;
+            let vod_detail = Arc::new(self.get_vod_detail(vod_id).await?);
+            Ok(self.chunk_stream(vod_detail, &options, token))
+        })
+        .flat_map(|prepared| match prepared {
+            Ok(chunks) => chunks.map(Ok).left_stream(),
+            Err(e) => stream::iter([Err(e)]).right_stream(),
+        });
+
+        chunks.flat_map(move |chunk| {
+            let items = match chunk {
+                Ok(chunk) => {
+                    on_progress(chunk.progress);
+                    // 건너뛴 요소가 있으면 구간의 이벤트보다 먼저 알립니다.
+                    let warnings = chunk.warnings.into_iter().map(VODChatItem::Warning);
+                    let items: Vec<VODChatItem> = match chunk.result {
+                        Ok(mut events) => {
+                            events.sort_by_key(|event| event.meta().received_time);
+                            warnings
+                                .chain(
+                                    events
+                                        .into_iter()
+                                        .map(|event| VODChatItem::Event(Box::new(event))),
+                                )
+                                .collect()
                         }
-                    }
+                        Err(error) => warnings
+                            .chain([VODChatItem::ChunkFailed {
+                                range: chunk.range,
+                                error,
+                            }])
+                            .collect(),
+                    };
+                    items.into_iter().map(Ok).collect()
                 }
-                Err(e) => {
-                    eprintln!("채팅 조회 오류 (시간 {}초): {}", current_time, e);
+                Err(e) => vec![Err(e)],
+            };
+            stream::iter(items)
+        })
+    }
+
+    /// 구간을 동시에 요청하고, 결과를 요청 순서대로 전달하는 스트림을 만듭니다.
+    fn chunk_stream<'a>(
+        &'a self,
+        vod_detail: Arc<VODDetail>,
+        options: &VODChatOptions,
+        mut token: VODChatResumeToken,
+    ) -> impl Stream<Item = VODChatChunk> + use<'a> {
+        let ranges = plan_chunks(&vod_detail.files, options.chunk_seconds, &token);
+        let chunk_count = ranges.len();
+        let durations: Vec<u64> = vod_detail
+            .files
+            .iter()
+            .map(|file| file.duration / 1_000_000)
+            .collect();
+        let clocks = file_clocks(&vod_detail);
+        let chunk_options = ChunkOptions {
+            retry: options.retry.clone(),
+            parse_mode: options.parse_mode,
+        };
+
+        // buffered는 동시에 요청하면서도 결과를 요청 순서대로 돌려줍니다.
+        stream::iter(ranges)
+            .map(move |range| {
+                let vod_detail = Arc::clone(&vod_detail);
+                let chunk_options = chunk_options.clone();
+                let clock = clocks.get(range.file_index).copied();
+                async move {
+                    let result = match (vod_detail.files.get(range.file_index), clock) {
+                        (Some(file), Some(clock)) => {
+                            self.get_chunk_chat_events(file, clock, range, &chunk_options)
+                                .await
+                        }
+                        _ => Err(Error::InvalidConfig(format!(
+                            "VOD에 없는 파일 위치입니다: {}",
+                            range.file_index
+                        ))),
+                    };
+                    (range, result)
                 }
-            }
-            current_time += chunk_size_seconds;
-        }
+            })
+            .buffered(options.concurrency.max(1))
+            .enumerate()
+            .map(move |(chunk_index, (range, result))| {
+                token.record(range, result.is_ok());
+                let (result, warnings) = match result {
+                    Ok(parsed) => (Ok(parsed.events), parsed.warnings),
+                    Err(e) => (Err(e), Vec::new()),
+                };
+                VODChatChunk {
+                    range,
+                    result,
+                    warnings,
+                    progress: VODChatProgress {
+                        chunk_index,
+                        chunk_count,
+                        seconds_covered: durations[..token.file_index.min(durations.len())]
+                            .iter()
+                            .sum::<u64>()
+                            + token.offset,
+                        total_seconds: durations.iter().sum(),
+                    },
+                    resume_token: token.clone(),
+                }
+            })
+    }
+
+    pub async fn get_full_vod_chat(&self, vod_id: u64) -> Result<VODChat> {
+        self.get_full_vod_chat_with(vod_id, &VODChatOptions::default())
+            .await
+    }
+
+    /// VOD 전체 채팅을 내려받아 모읍니다. 실패한 구간은 결과의 `failed`로 알려줍니다.
+    /// 재개 토큰을 지정한 경우, 이번에 내려받은 구간의 이벤트만 담깁니다.
+    pub async fn get_full_vod_chat_with(
+        &self,
+        vod_id: u64,
+        options: &VODChatOptions,
+    ) -> Result<VODChat> {
+        let mut events = Vec::new();
+        let mut failed = Vec::new();
+        let mut warnings = Vec::new();
+
+        let resume_token = self
+            .download_vod_chat(vod_id, options, |mut chunk| {
+                warnings.append(&mut chunk.warnings);
+                match chunk.result {
+                    Ok(mut chunk_events) => events.append(&mut chunk_events),
+                    Err(e) => failed.push(VODChatFailure {
+                        range: chunk.range,
+                        reason: e.to_string(),
+                    }),
+                }
+            })
+            .await?;
+
+        Ok(VODChat {
+            events,
+            failed,
+            warnings,
+            resume_token,
+        })
+    }
+}
+
+/// 옵션의 재개 토큰을 확인하고, 없으면 처음부터 내려받는 토큰을 만듭니다.
+/// 옵션도 이때 함께 확인합니다.
+fn resume_token(vod_id: u64, options: &VODChatOptions) -> Result<VODChatResumeToken> {
+    options.validate()?;
+    match &options.resume {
+        Some(token) if token.vod_id != vod_id => Err(Error::InvalidConfig(format!(
+            "다른 VOD의 재개 토큰입니다: {}",
+            token.vod_id
+        ))),
+        Some(token) => Ok(token.clone()),
+        None => Ok(VODChatResumeToken::new(vod_id)),
+    }
+}
+
+/// 구간 하나를 내려받을 때 필요한 옵션
+#[derive(Clone)]
+struct ChunkOptions {
+    retry: RetryPolicy,
+    parse_mode: VODChatParseMode,
+}
+
+/// 구간 하나의 채팅 XML을 파싱합니다. 채팅이 없는 구간은 빈 응답이 옵니다.
+///
+/// 응답에 구간 밖의 채팅이 섞여 있으면 이웃한 구간과 겹치지 않도록 버립니다.
+/// 파일의 마지막 구간은 끝을 제한하지 않습니다.
+fn parse_chunk_xml(
+    xml_content: &str,
+    clock: VodClock,
+    range: VODChatRange,
+    file: &VODFile,
+    mode: VODChatParseMode,
+) -> Result<ParsedVODChat> {
+    if xml_content.trim().is_empty() {
+        return Ok(ParsedVODChat::default());
+    }
+
+    let mut parsed = parse_vod_chat_xml_with_clock(xml_content, clock, mode)?;
+
+    let is_last = range.end >= file.duration / 1_000_000;
+    parsed.events.retain(|event| {
+        let Some(vod_offset) = event.meta().vod_offset else {
+            return true;
+        };
+        let time = vod_offset - clock.file_offset;
+        (range.start == 0 || time >= range.start as f64) && (is_last || time < range.end as f64)
+    });
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{MockResponse, spawn_http_server};
+    use crate::vod_chat::MAX_CHUNK_SECONDS;
+
+    fn login_client(login: String) -> SoopHttpClient {
+        SoopHttpClient::builder()
+            .base_urls(SoopApiBaseUrls {
+                login,
+                ..Default::default()
+            })
+            .retry_policy(RetryPolicy {
+                max_retries: 2,
+                initial_backoff: Duration::from_millis(1),
+                max_backoff: Duration::from_millis(1),
+            })
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_login_returns_ticket_cookie() {
+        let (login, requests) = spawn_http_server(|_| {
+            MockResponse::ok(r#"{"RESULT":1}"#)
+                .header("Set-Cookie", "PdboxTicket=ticket123; Path=/")
+        })
+        .await;
+
+        let auth = login_client(login).login("user", "pass").await.unwrap();
+        assert_eq!(auth.user_id, "user");
+        assert_eq!(auth.ticket, "ticket123");
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].path, LOGIN_API_PATH);
+        assert!(requests[0].body.contains("szUid=user"));
+    }
+
+    #[tokio::test]
+    async fn test_login_failures_are_not_retried() {
+        let (login, requests) = spawn_http_server(|_| MockResponse::status(500)).await;
+        let client = login_client(login);
+        assert!(matches!(
+            client.login("user", "pass").await,
+            Err(Error::Request(_))
+        ));
+        // 5xx 응답이라도 로그인 요청은 다시 보내지 않습니다.
+        assert_eq!(requests.lock().unwrap().len(), 1);
+
+        let (login, _) = spawn_http_server(|_| MockResponse::ok(r#"{"RESULT":-1}"#)).await;
+        assert!(matches!(
+            login_client(login).login("user", "pass").await,
+            Err(Error::LoginFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_retry_backoff_doubles_and_caps() {
+        let policy = RetryPolicy {
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(3),
+        };
+
+        let delays: Vec<u128> = (1..=5).map(|n| policy.backoff(n).as_millis()).collect();
+        assert_eq!(delays, vec![500, 1000, 2000, 3000, 3000]);
+    }
 
-        Ok(all_events)
+    #[tokio::test]
+    async fn test_failed_chunks_are_reported_in_order() {
+        let (chat_server, requests) = spawn_http_server(|_| MockResponse::status(500)).await;
+        // 클라이언트의 재시도 정책은 구간 조회에 적용되지 않습니다.
+        let client = login_client(String::new());
+        let vod_detail = VODDetail {
+            id: "1".to_string(),
+            title: String::new(),
+            channel_id: String::new(),
+            broad_start: "2024-01-01 00:00:00".to_string(),
+            files: vec![VODFile {
+                id: 1,
+                order: 1,
+                file_key: String::new(),
+                file_start: String::new(),
+                chat: format!("{}/chat?key=1", chat_server),
+                duration: 250 * 1_000_000,
+            }],
+        };
+        let options = VODChatOptions {
+            chunk_seconds: 100,
+            retry: RetryPolicy {
+                max_retries: 1,
+                initial_backoff: Duration::from_millis(1),
+                max_backoff: Duration::from_millis(1),
+            },
+            ..Default::default()
+        };
+
+        let chunks: Vec<VODChatChunk> = client
+            .chunk_stream(Arc::new(vod_detail), &options, VODChatResumeToken::new(1))
+            .collect()
+            .await;
+
+        // 서버 오류로 모든 구간이 실패로 남고, 진행 위치는 끝까지 나아갑니다.
+        let starts: Vec<u64> = chunks.iter().map(|chunk| chunk.range.start).collect();
+        assert_eq!(starts, vec![0, 100, 200]);
+        assert!(chunks.iter().all(|chunk| chunk.result.is_err()));
+        // 구간마다 첫 요청과 재시도 한 번만 보냅니다.
+        assert_eq!(requests.lock().unwrap().len(), 6);
+
+        let last = chunks.last().unwrap();
+        assert_eq!(last.progress.chunk_index, 2);
+        assert_eq!(last.progress.seconds_covered, 250);
+        assert_eq!(last.progress.total_seconds, 250);
+        assert_eq!(last.resume_token.failed.len(), 3);
+        assert_eq!(
+            (last.resume_token.file_index, last.resume_token.offset),
+            (0, 250)
+        );
     }
 
-    pub async fn get_full_vod_chat(&self, vod_id: u64) -> Result<Vec<Event>> {
-        let vod_detail = self.get_vod_detail(vod_id).await?;
-        let mut all_events = Vec::new();
+    #[tokio::test]
+    async fn test_chunk_client_errors_are_not_retried() {
+        let (chat_server, requests) = spawn_http_server(|_| MockResponse::status(404)).await;
+        let client = login_client(String::new());
+        let vod_detail = VODDetail {
+            id: "1".to_string(),
+            title: String::new(),
+            channel_id: String::new(),
+            broad_start: "2024-01-01 00:00:00".to_string(),
+            files: vec![VODFile {
+                id: 1,
+                order: 1,
+                file_key: String::new(),
+                file_start: String::new(),
+                chat: format!("{}/chat?key=1", chat_server),
+                duration: 150 * 1_000_000,
+            }],
+        };
+        let options = VODChatOptions {
+            chunk_seconds: 100,
+            retry: RetryPolicy {
+                max_retries: 3,
+                initial_backoff: Duration::from_millis(1),
+                max_backoff: Duration::from_millis(1),
+            },
+            ..Default::default()
+        };
+
+        let chunks: Vec<VODChatChunk> = client
+            .chunk_stream(Arc::new(vod_detail), &options, VODChatResumeToken::new(1))
+            .collect()
+            .await;
+
+        // 4xx 응답은 다시 보내도 같으므로 구간마다 한 번만 요청합니다.
+        assert!(chunks.iter().all(|chunk| chunk.result.is_err()));
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
 
-        for (_, file) in vod_detail.files.iter().enumerate() {
-            let mut file_events = self
-                .get_file_chat_events(file, &vod_detail.broad_start, 300)
-                .await?; // 5분 간격
-            all_events.append(&mut file_events);
+    #[tokio::test]
+    async fn test_stream_reports_failed_chunks_as_items() {
+        let (chat_server, _) = spawn_http_server(|_| MockResponse::status(500)).await;
+        let vod_detail = format!(
+            r#"{{"result":1,"data":{{"title_no":1,"full_title":"VOD","bj_id":"streamer",
+            "broad_start":"2024-01-01 00:00:00","files":[{{"idx":1,"file_order":1,
+            "file_info_key":"","file_start":"","chat":"{}/chat?key=1","duration":150000000}}]}}}}"#,
+            chat_server
+        );
+        let (mobile, _) = spawn_http_server(move |_| MockResponse::ok(vod_detail.clone())).await;
+        let client = SoopHttpClient::with_base_urls(SoopApiBaseUrls {
+            mobile,
+            ..Default::default()
+        });
+        let options = VODChatOptions {
+            chunk_seconds: 100,
+            retry: RetryPolicy::disabled(),
+            ..Default::default()
+        };
+
+        let items: Vec<Result<VODChatItem>> = client
+            .stream_vod_chat_with(1, options, |_| {})
+            .collect()
+            .await;
+
+        // 실패한 구간은 오류가 아닌 항목으로 전달되고, 스트림은 끝까지 진행합니다.
+        let failed: Vec<u64> = items
+            .iter()
+            .map(|item| match item {
+                Ok(VODChatItem::ChunkFailed { range, .. }) => range.start,
+                item => panic!("unexpected item: {item:?}"),
+            })
+            .collect();
+        assert_eq!(failed, vec![0, 100]);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_chunk_length_is_rejected() {
+        for chunk_seconds in [0, MAX_CHUNK_SECONDS + 1] {
+            let options = VODChatOptions {
+                chunk_seconds,
+                ..Default::default()
+            };
+            let result = SoopHttpClient::new()
+                .download_vod_chat(1, &options, |_| {})
+                .await;
+            assert!(
+                matches!(result, Err(Error::InvalidConfig(_))),
+                "{chunk_seconds}"
+            );
         }
+    }
 
-        println!("총 {}개 이벤트 수집 완료", all_events.len());
-        Ok(all_events)
+    #[test]
+    fn test_builder_rejects_invalid_user_agent() {
+        let result = SoopHttpClient::builder().user_agent("bad\nagent").build();
+        assert!(matches!(result, Err(Error::InvalidConfig(_))));
     }
 }
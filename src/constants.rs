@@ -1,2 +1,14 @@
-pub const PLAYER_LIVE_API_URL: &str = "https://live.sooplive.co.kr/afreeca/player_live_api.php";
-pub const EMOTICON_API_URL: &str = "https://live.sooplive.co.kr/api/signature_emoticon_api.php";
+// --- 기본 API 서버 주소 ---
+pub const LIVE_API_BASE_URL: &str = "https://live.sooplive.co.kr";
+pub const CHANNEL_API_BASE_URL: &str = "https://chapi.sooplive.co.kr";
+pub const MOBILE_API_BASE_URL: &str = "https://api.m.sooplive.co.kr";
+pub const LOGIN_API_BASE_URL: &str = "https://login.sooplive.co.kr";
+
+// --- API 경로 ---
+pub const PLAYER_LIVE_API_PATH: &str = "/afreeca/player_live_api.php";
+pub const EMOTICON_API_PATH: &str = "/api/signature_emoticon_api.php";
+pub const LOGIN_API_PATH: &str = "/app/LoginAction.php";
+pub const VOD_DETAIL_API_PATH: &str = "/station/video/a/view";
+
+pub const DEFAULT_USER_AGENT: &str = "Mozilla/5.0 (compatible; SoopClient/1.0)";
+pub const AUTH_TICKET_COOKIE: &str = "PdboxTicket";
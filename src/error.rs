@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::chat::constants::message_codes::MessageCode;
+
 // 모든 아이템을 pub로 만들어 다른 모듈에서 가져다 쓸 수 있게 합니다.
 #[derive(Debug, Error)]
 pub enum Error {
@@ -9,8 +11,9 @@ pub enum Error {
     #[error("WebSocket 연결 실패: {0}")]
     ConnectionFailed(String),
 
+    // 오류 타입이 커지지 않도록 Box로 감쌉니다.
     #[error("WebSocket 통신 오류: {0}")]
-    WebSocket(#[from] tokio_tungstenite::tungstenite::Error),
+    WebSocket(Box<tokio_tungstenite::tungstenite::Error>),
 
     #[error("JSON 파싱 실패: {0}")]
     ResponseJson(reqwest::Error),
@@ -18,12 +21,18 @@ pub enum Error {
     #[error("JSON 파싱 실패: {0}")]
     SerdeJson(serde_json::Error),
 
+    #[error("TLS 설정 오류: {0}")]
+    Tls(#[from] rustls::Error),
+
     #[error("내부 채널 통신 오류: {0}")]
     InternalChannel(String),
 
     #[error("방송이 꺼져있습니다.")]
     StreamOffline,
 
+    #[error("방송이 재시작되어 채팅방이 변경되었습니다.")]
+    ChatRoomChanged,
+
     #[error("잘못된 URL 형식: {0}")]
     URLParse(#[from] url::ParseError),
 
@@ -33,8 +42,85 @@ pub enum Error {
     #[error("이미 시작된 연결입니다.")]
     AlreadyStarted,
 
+    #[error("연결이 종료되었습니다.")]
+    ConnectionClosed,
+
+    #[error("채팅 서버에 연결되어 있지 않습니다.")]
+    NotConnected,
+
+    #[error("명령 전송 실패: {0}")]
+    CommandFailed(String),
+
+    #[error("서버가 명령을 거부했습니다 (code: {code}): {reason}")]
+    CommandRejected { code: u32, reason: String },
+
+    #[error("명령에 대한 서버의 응답이 없습니다.")]
+    CommandTimeout,
+
+    #[error("이미 추가된 채널입니다: {0}")]
+    ChannelAlreadyAdded(String),
+
+    #[error("채널을 찾을 수 없습니다: {0}")]
+    ChannelNotFound(String),
+
+    #[error("잘못된 설정: {0}")]
+    InvalidConfig(String),
+
     #[error("API 오류: {0}")]
     ApiError(String),
+
+    #[error("메시지 파싱 실패 (code: {code}, field: {field}, body_len: {body_len})")]
+    ParseError {
+        code: MessageCode,
+        field: String,
+        body_len: usize,
+    },
+
+    #[error("패킷 해석 실패: {0}")]
+    Frame(#[from] FrameError),
+
+    #[error("로그인 실패: {0}")]
+    LoginFailed(String),
+
+    #[error("로그인이 필요한 기능입니다.")]
+    NotAuthenticated,
+
+    #[error("파일 입출력 오류: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("잘못된 녹화 파일: {0}")]
+    InvalidCapture(String),
+
+    #[error("녹화 중 패킷 {skipped}개를 놓쳤습니다.")]
+    CaptureLagged { skipped: u64 },
+
+    #[error("XML 파싱 실패: {0}")]
+    Xml(#[from] quick_xml::Error),
+
+    #[error("VOD 채팅 요소 해석 실패 (offset: {offset}): {reason}")]
+    VodChat { offset: u64, reason: String },
+}
+
+/// 채팅 패킷의 헤더를 해석하지 못했을 때의 오류입니다.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum FrameError {
+    #[error("헤더보다 짧은 패킷입니다 (len: {len})")]
+    TooShort { len: usize },
+
+    #[error("패킷 시작 바이트가 올바르지 않습니다")]
+    InvalidStarter,
+
+    #[error("헤더의 {field} 필드가 숫자가 아닙니다")]
+    InvalidHeader { field: &'static str },
+
+    #[error("본문 길이가 헤더와 다릅니다 (declared: {declared}, actual: {actual})")]
+    BodyLengthMismatch { declared: usize, actual: usize },
+}
+
+impl From<tokio_tungstenite::tungstenite::Error> for Error {
+    fn from(e: tokio_tungstenite::tungstenite::Error) -> Self {
+        Error::WebSocket(Box::new(e))
+    }
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -3,10 +3,19 @@ pub mod client;
 mod constants;
 pub mod error;
 pub mod models;
+#[cfg(test)]
+mod test_support;
+pub mod vod_chat;
 pub mod vod_chat_parser;
 
 pub use chat::events::Event;
-pub use client::SoopHttpClient;
+pub use client::{RetryPolicy, SoopApiBaseUrls, SoopHttpClient, SoopHttpClientBuilder};
 pub use error::{Error, Result};
 pub use models::{VOD, VODDetail, VODFile};
-pub use vod_chat_parser::parse_vod_chat_xml_with_start_time;
+pub use vod_chat::{
+    VODChat, VODChatItem, VODChatOptions, VODChatProgress, VODChatResumeToken,
+};
+pub use vod_chat_parser::{
+    ParsedVODChat, VODChatParseMode, parse_vod_chat_xml, parse_vod_chat_xml_with_start_time,
+    parse_vod_file_chat_xml,
+};
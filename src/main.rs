@@ -2,8 +2,9 @@ use std::sync::Arc;
 
 use soup_sdk::{
     SoopHttpClient,
-    chat::{Event, SoopChatConnection, SoopChatOptions, commands::MessageType},
+    chat::{Event, SoopChatConnection, SoopChatOptions},
 };
+use tokio::sync::broadcast::error::RecvError;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -18,6 +19,7 @@ async fn main() -> anyhow::Result<()> {
     let options = SoopChatOptions {
         streamer_id: "inehine".to_string(),
         password: "".to_string(),
+        ..Default::default()
     };
 
     // let (_, ooo) = soop_client
@@ -49,9 +51,11 @@ async fn main() -> anyhow::Result<()> {
     loop {
         match event_receiver.recv().await {
             Ok(event) => handle_event(event),
-            Err(_) => {
-                break;
+            // 이벤트 처리가 밀려 놓친 경우에는 알리고 계속 수신합니다.
+            Err(RecvError::Lagged(skipped)) => {
+                eprintln!("[System] {}개의 이벤트를 놓쳤습니다.", skipped);
             }
+            Err(RecvError::Closed) => break,
         }
     }
 
@@ -63,12 +67,18 @@ fn handle_event(event: Event) {
         Event::Chat(e) => {
             println!("채팅     {:<10} {}", e.user.id, e.comment)
         }
-        Event::Join(v) => {
+        Event::Join(_v) => {
             // println!("{:?}", v)
         }
-        Event::Disconnected => {
+        Event::Disconnected(_) => {
             println!("정상 종료됨");
         }
+        Event::Reconnecting(e) => {
+            println!("재연결    {}회차 {:?} 후 시도", e.attempt, e.delay)
+        }
+        Event::Reconnected(_) => {
+            println!("재연결 성공");
+        }
         Event::Donation(d) => {
             println!("별풍선    {} {}", d.from_label, d.amount)
         }
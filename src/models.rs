@@ -20,6 +20,28 @@ pub struct LiveDetail {
     pub streamer_nick: String,
     pub title: String,
     pub categories: Vec<String>,
+    /// 비밀번호 방송 여부
+    #[serde(default)]
+    pub is_password: bool,
+    /// 연령 제한(19세) 방송 여부
+    #[serde(default)]
+    pub is_adult: bool,
+}
+
+// --- 로그인 관련 구조체들 ---
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RawLoginResponse {
+    #[serde(rename = "RESULT")]
+    pub result: i32, // 1이면 로그인 성공
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthTicket {
+    pub user_id: String,
+    /// 채팅 서버 인증에 사용하는 티켓 (PdboxTicket 쿠키)
+    pub ticket: String,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -51,6 +73,10 @@ pub struct ChannelInfo {
     pub title: String,
     #[serde(rename = "CATEGORY_TAGS")]
     pub categories: Vec<String>,
+    #[serde(rename = "BPWD", default)]
+    pub bpwd: String, // "Y"이면 비밀번호 방송
+    #[serde(rename = "GRADE", default, deserialize_with = "as_u64")]
+    pub grade: u64, // 19이면 연령 제한 방송
 }
 
 impl RawVODResponse {
@@ -250,12 +276,14 @@ pub struct RawVODDetailFile {
     duration: u64,
 }
 
+/// SOOP API의 시간 문자열(한국 시간, `%Y-%m-%d %H:%M:%S`)을 UTC로 변환합니다.
+/// 형식이 맞지 않으면 패닉이 발생합니다.
 pub fn parse_soop_timestamp(timestamp: &str) -> DateTime<Utc> {
-    let mut date = chrono::NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S")
-        .map_err(|e| anyhow::anyhow!("Failed to parse: {}", e))
-        .unwrap()
-        .and_utc();
+    try_parse_soop_timestamp(timestamp).unwrap_or_else(|| panic!("Failed to parse: {}", timestamp))
+}
 
-    date = date - chrono::Duration::hours(9);
-    date
+/// `parse_soop_timestamp`와 같지만, 형식이 맞지 않으면 `None`을 반환합니다.
+pub fn try_parse_soop_timestamp(timestamp: &str) -> Option<DateTime<Utc>> {
+    let date = chrono::NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S").ok()?;
+    Some(date.and_utc() - chrono::Duration::hours(9))
 }
@@ -0,0 +1,167 @@
+//! 테스트에서 SOOP API와 채팅 서버 대신 사용하는 mock 서버입니다.
+
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::WebSocketStream;
+use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+
+/// mock 서버가 받은 HTTP 요청
+#[derive(Debug, Clone)]
+pub(crate) struct MockRequest {
+    /// 쿼리를 포함한 경로
+    pub path: String,
+    pub body: String,
+}
+
+/// mock 서버가 돌려줄 HTTP 응답
+#[derive(Debug, Clone)]
+pub(crate) struct MockResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl MockResponse {
+    pub fn ok(body: impl Into<String>) -> Self {
+        Self {
+            status: 200,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    pub fn status(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: String::new(),
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+/// 받은 요청 목록, 요청 순서대로 쌓입니다.
+pub(crate) type MockRequests = Arc<Mutex<Vec<MockRequest>>>;
+
+/// 요청마다 `handler`의 응답을 돌려주는 HTTP 서버를 띄우고, 서버 주소를 반환합니다.
+pub(crate) async fn spawn_http_server<F>(handler: F) -> (String, MockRequests)
+where
+    F: Fn(&MockRequest) -> MockResponse + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = format!("http://{}", listener.local_addr().unwrap());
+    let requests = MockRequests::default();
+    let handler = Arc::new(handler);
+
+    let log = Arc::clone(&requests);
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let handler = Arc::clone(&handler);
+            let log = Arc::clone(&log);
+            tokio::spawn(async move {
+                let Some(request) = read_request(stream).await else {
+                    return;
+                };
+                let (request, mut stream) = request;
+                let response = handler(&request);
+                log.lock().unwrap().push(request);
+                write_response(&mut stream, response).await;
+            });
+        }
+    });
+
+    (address, requests)
+}
+
+/// WebSocket 연결마다 `handler`를 실행하는 채팅 서버를 띄우고, 접속 주소를 반환합니다.
+pub(crate) async fn spawn_ws_server<F, Fut>(handler: F) -> String
+where
+    F: Fn(WebSocketStream<TcpStream>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = format!("ws://{}/Websocket/streamer", listener.local_addr().unwrap());
+
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            if let Ok(ws_stream) = tokio_tungstenite::accept_hdr_async(stream, accept_chat).await {
+                tokio::spawn(handler(ws_stream));
+            }
+        }
+    });
+
+    address
+}
+
+/// 클라이언트가 요청한 "chat" 하위 프로토콜로 응답해야 접속이 완료됩니다.
+// 오류 타입은 tungstenite의 콜백 시그니처를 따릅니다.
+#[allow(clippy::result_large_err)]
+fn accept_chat(_: &Request, mut response: Response) -> Result<Response, ErrorResponse> {
+    response
+        .headers_mut()
+        .insert("Sec-WebSocket-Protocol", HeaderValue::from_static("chat"));
+    Ok(response)
+}
+
+/// 방송 중인 채널의 방송 정보 응답
+pub(crate) fn live_detail_json(chat_no: &str) -> String {
+    format!(
+        r#"{{"CHANNEL":{{"RESULT":1,"CHDOMAIN":"127.0.0.1","CHPT":"8000","CHATNO":"{}","BJNICK":"스트리머","TITLE":"방송","CATEGORY_TAGS":[]}}}}"#,
+        chat_no
+    )
+}
+
+async fn read_request(stream: TcpStream) -> Option<(MockRequest, TcpStream)> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line).await.ok()?;
+    let path = line.split_whitespace().nth(1)?.to_string();
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header).await.ok()?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            content_length = value.trim().parse().ok()?;
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).await.ok()?;
+
+    let request = MockRequest {
+        path,
+        body: String::from_utf8_lossy(&body).to_string(),
+    };
+    Some((request, reader.into_inner()))
+}
+
+async fn write_response(stream: &mut TcpStream, response: MockResponse) {
+    let mut head = format!(
+        "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        response.body.len()
+    );
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str("\r\n");
+
+    let _ = stream.write_all(head.as_bytes()).await;
+    let _ = stream.write_all(response.body.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
@@ -0,0 +1,335 @@
+use serde::{Deserialize, Serialize};
+
+use crate::chat::events::Event;
+use crate::client::RetryPolicy;
+use crate::error::{Error, Result};
+use crate::models::{VODDetail, VODFile, try_parse_soop_timestamp};
+use crate::vod_chat_parser::{VODChatParseMode, VodClock};
+
+/// 구간 하나에 요청할 수 있는 최대 길이(초), 서버는 요청한 시작 시간부터 이 길이만큼의 채팅만 돌려줍니다.
+pub const MAX_CHUNK_SECONDS: u64 = 300;
+
+/// VOD 채팅을 내려받는 방식입니다.
+#[derive(Debug, Clone)]
+pub struct VODChatOptions {
+    /// 동시에 요청할 구간 수 (기본 4)
+    pub concurrency: usize,
+    /// 한 번에 요청할 구간 길이, 초 단위 (기본 300초, 최대 `MAX_CHUNK_SECONDS`)
+    pub chunk_seconds: u64,
+    /// 구간 하나의 조회가 실패했을 때의 재시도 정책, `RetryPolicy`와 같이 5xx 응답과 일시적인 오류만 재시도합니다.
+    /// 구간 조회에는 클라이언트의 재시도 정책(`SoopHttpClientBuilder::retry_policy`) 대신 이 정책만 적용됩니다.
+    pub retry: RetryPolicy,
+    /// 해석하지 못한 XML 요소의 처리 방식 (기본 `Lenient`)
+    /// `Strict`이면 그런 요소가 있는 구간 전체가 실패로 기록됩니다.
+    pub parse_mode: VODChatParseMode,
+    /// 이전 다운로드에서 저장한 재개 토큰, 지정하면 이어서 내려받습니다.
+    pub resume: Option<VODChatResumeToken>,
+}
+
+impl Default for VODChatOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: 4,
+            chunk_seconds: 300,
+            retry: RetryPolicy::default(),
+            parse_mode: VODChatParseMode::default(),
+            resume: None,
+        }
+    }
+}
+
+impl VODChatOptions {
+    /// 구간 길이가 0이거나 서버가 돌려주는 범위를 넘으면 오류를 반환합니다.
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.chunk_seconds == 0 {
+            return Err(Error::InvalidConfig(
+                "chunk_seconds는 0보다 커야 합니다.".to_string(),
+            ));
+        }
+        if self.chunk_seconds > MAX_CHUNK_SECONDS {
+            return Err(Error::InvalidConfig(format!(
+                "chunk_seconds는 {}초를 넘을 수 없습니다: {}",
+                MAX_CHUNK_SECONDS, self.chunk_seconds
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// VOD 파일 안의 채팅 구간, `start`부터 `end` 직전까지 (초 단위)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VODChatRange {
+    /// `VODDetail::files`에서의 파일 위치
+    pub file_index: usize,
+    pub start: u64,
+    pub end: u64,
+}
+
+/// 다운로드를 이어서 하기 위한 진행 상황입니다.
+///
+/// `file_index`, `offset` 이전의 구간은 모두 처리되었으며,
+/// 그 중 실패한 구간은 `failed`에 남아 있습니다.
+/// 재개하면 실패한 구간부터 다시 요청한 뒤, 남은 구간을 이어서 내려받습니다.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VODChatResumeToken {
+    pub vod_id: u64,
+    pub file_index: usize,
+    pub offset: u64,
+    pub failed: Vec<VODChatRange>,
+}
+
+impl VODChatResumeToken {
+    pub fn new(vod_id: u64) -> Self {
+        Self {
+            vod_id,
+            file_index: 0,
+            offset: 0,
+            failed: Vec::new(),
+        }
+    }
+
+    /// 실패한 구간 없이 모두 내려받았는지 여부
+    /// 다운로드가 끝난 뒤의 토큰에서만 의미가 있습니다.
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+
+    /// 구간 처리 결과를 반영합니다.
+    pub(crate) fn record(&mut self, range: VODChatRange, success: bool) {
+        self.failed.retain(|failed| *failed != range);
+        if !success {
+            self.failed.push(range);
+        }
+
+        // 다시 요청한 실패 구간은 진행 위치보다 앞에 있으므로 위치를 바꾸지 않습니다.
+        if (range.file_index, range.start) >= (self.file_index, self.offset) {
+            self.file_index = range.file_index;
+            self.offset = range.end;
+        }
+    }
+}
+
+/// 다운로드 진행 상황
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VODChatProgress {
+    /// 이번 다운로드에서 처리한 구간의 순서 (0부터 시작)
+    pub chunk_index: usize,
+    /// 이번 다운로드에서 요청할 전체 구간 수
+    pub chunk_count: usize,
+    /// VOD 시작부터 처리가 끝난 위치까지의 길이, 초 단위
+    pub seconds_covered: u64,
+    /// VOD 전체 길이, 초 단위
+    pub total_seconds: u64,
+}
+
+/// 처리가 끝난 구간 하나의 결과, 요청 순서대로 전달됩니다.
+#[derive(Debug)]
+pub struct VODChatChunk {
+    pub range: VODChatRange,
+    /// 재시도 후에도 실패했다면 마지막 오류
+    pub result: Result<Vec<Event>>,
+    /// 느슨한 모드에서 건너뛴 요소의 오류
+    pub warnings: Vec<Error>,
+    pub progress: VODChatProgress,
+    /// 이 구간까지 반영한 재개 토큰, 저장해 두면 중단된 뒤에 이어서 내려받을 수 있습니다.
+    pub resume_token: VODChatResumeToken,
+}
+
+/// VOD 채팅 스트림(`SoopHttpClient::stream_vod_chat`)으로 전달되는 항목입니다.
+///
+/// 경고와 실패한 구간은 알린 뒤 다운로드를 계속합니다.
+#[derive(Debug)]
+pub enum VODChatItem {
+    /// 채팅 이벤트
+    Event(Box<Event>),
+    /// 느슨한 모드에서 건너뛴 요소의 오류, 구간의 이벤트보다 먼저 전달됩니다.
+    Warning(Error),
+    /// 재시도 후에도 내려받지 못한 구간과 마지막 오류
+    ChunkFailed { range: VODChatRange, error: Error },
+}
+
+/// 내려받지 못한 구간과 그 이유
+#[derive(Debug, Clone)]
+pub struct VODChatFailure {
+    pub range: VODChatRange,
+    pub reason: String,
+}
+
+/// VOD 전체 채팅을 내려받은 결과입니다.
+#[derive(Debug)]
+pub struct VODChat {
+    pub events: Vec<Event>,
+    /// 재시도 후에도 실패한 구간, 비어 있지 않다면 `events`에 빠진 구간이 있습니다.
+    pub failed: Vec<VODChatFailure>,
+    /// 느슨한 모드에서 건너뛴 요소의 오류, 비어 있지 않다면 `events`에 빠진 이벤트가 있습니다.
+    pub warnings: Vec<Error>,
+    pub resume_token: VODChatResumeToken,
+}
+
+/// 요청할 구간 목록을 만듭니다. 재개 토큰이 있으면 실패한 구간을 시간 순서대로 먼저 요청합니다.
+/// `chunk_seconds`는 `VODChatOptions::validate`로 확인한 값이어야 합니다.
+pub(crate) fn plan_chunks(
+    files: &[VODFile],
+    chunk_seconds: u64,
+    resume: &VODChatResumeToken,
+) -> Vec<VODChatRange> {
+    // 다시 실패한 구간은 목록 끝으로 옮겨지므로, 시간 순서대로 요청하도록 정렬합니다.
+    let mut ranges = resume.failed.clone();
+    ranges.sort_by_key(|range| (range.file_index, range.start));
+
+    for (file_index, file) in files.iter().enumerate().skip(resume.file_index) {
+        let duration_seconds = file.duration / 1_000_000; // 마이크로초를 초로 변환
+        let mut start = if file_index == resume.file_index {
+            resume.offset
+        } else {
+            0
+        };
+
+        while start < duration_seconds {
+            let end = (start + chunk_seconds).min(duration_seconds);
+            ranges.push(VODChatRange {
+                file_index,
+                start,
+                end,
+            });
+            start = end;
+        }
+    }
+
+    ranges
+}
+
+/// 파일마다 채팅 시간의 기준을 계산합니다.
+///
+/// 재생 시간은 앞선 파일 길이의 합이며, 파일 시작 시간을 알 수 없으면
+/// 첫 파일은 방송 시작 시간을, 나머지는 앞 파일이 끝나는 시간을 사용합니다.
+pub(crate) fn file_clocks(vod_detail: &VODDetail) -> Vec<VodClock> {
+    let mut clocks: Vec<VodClock> = Vec::with_capacity(vod_detail.files.len());
+    let mut file_offset = 0.0;
+
+    for file in &vod_detail.files {
+        let fallback = match clocks.last() {
+            Some(previous) => previous.file_start.map(|start| {
+                start + chrono::Duration::seconds((file_offset - previous.file_offset) as i64)
+            }),
+            None => try_parse_soop_timestamp(&vod_detail.broad_start),
+        };
+
+        clocks.push(VodClock {
+            file_start: try_parse_soop_timestamp(&file.file_start).or(fallback),
+            file_offset,
+        });
+        file_offset += file.duration as f64 / 1_000_000.0;
+    }
+
+    clocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(duration_seconds: u64) -> VODFile {
+        VODFile {
+            id: 0,
+            order: 0,
+            file_key: String::new(),
+            file_start: String::new(),
+            chat: String::new(),
+            duration: duration_seconds * 1_000_000,
+        }
+    }
+
+    fn range(file_index: usize, start: u64, end: u64) -> VODChatRange {
+        VODChatRange {
+            file_index,
+            start,
+            end,
+        }
+    }
+
+    #[test]
+    fn test_file_clocks_use_file_start() {
+        let mut first = file(3600);
+        first.file_start = "2024-01-01 13:00:00".to_string();
+        let mut second = file(1800);
+        second.file_start = "2024-01-01 14:10:00".to_string();
+        let vod_detail = VODDetail {
+            id: "1".to_string(),
+            title: String::new(),
+            channel_id: String::new(),
+            broad_start: "2024-01-01 12:59:00".to_string(),
+            files: vec![first, second, file(60)],
+        };
+
+        let clocks = file_clocks(&vod_detail);
+        let starts: Vec<_> = clocks
+            .iter()
+            .map(|clock| clock.file_start.map(|start| start.to_rfc3339()))
+            .collect();
+        let offsets: Vec<f64> = clocks.iter().map(|clock| clock.file_offset).collect();
+
+        // 시작 시간이 없는 세 번째 파일은 두 번째 파일이 끝나는 시간에 이어집니다.
+        assert_eq!(
+            starts,
+            vec![
+                Some("2024-01-01T04:00:00+00:00".to_string()),
+                Some("2024-01-01T05:10:00+00:00".to_string()),
+                Some("2024-01-01T05:40:00+00:00".to_string()),
+            ]
+        );
+        assert_eq!(offsets, vec![0.0, 3600.0, 5400.0]);
+    }
+
+    #[test]
+    fn test_resume_after_failed_chunk() {
+        let files = [file(250), file(100)];
+        let mut token = VODChatResumeToken::new(1);
+        let ranges = plan_chunks(&files, 100, &token);
+        assert_eq!(
+            ranges,
+            vec![
+                range(0, 0, 100),
+                range(0, 100, 200),
+                range(0, 200, 250),
+                range(1, 0, 100)
+            ]
+        );
+
+        // 두 번째 구간이 실패한 뒤 세 번째 구간까지 처리하고 중단된 경우
+        token.record(ranges[0], true);
+        token.record(ranges[1], false);
+        token.record(ranges[2], true);
+        assert_eq!((token.file_index, token.offset), (0, 250));
+        assert!(!token.is_complete());
+
+        // 실패한 구간을 먼저 다시 요청하고, 남은 구간을 이어서 요청합니다.
+        let ranges = plan_chunks(&files, 100, &token);
+        assert_eq!(ranges, vec![range(0, 100, 200), range(1, 0, 100)]);
+
+        token.record(ranges[0], true);
+        token.record(ranges[1], true);
+        assert_eq!((token.file_index, token.offset), (1, 100));
+        assert!(token.is_complete());
+        assert!(plan_chunks(&files, 100, &token).is_empty());
+    }
+
+    #[test]
+    fn test_failed_chunks_are_retried_in_time_order() {
+        let files = [file(300)];
+        let mut token = VODChatResumeToken::new(1);
+        for range in plan_chunks(&files, 100, &token).into_iter().take(2) {
+            token.record(range, false);
+        }
+
+        // 재개한 뒤 첫 구간만 다시 실패하고 중단되면, 토큰의 실패 목록 순서가 바뀝니다.
+        let ranges = plan_chunks(&files, 100, &token);
+        token.record(ranges[0], false);
+        assert_eq!(token.failed, vec![range(0, 100, 200), range(0, 0, 100)]);
+
+        assert_eq!(
+            plan_chunks(&files, 100, &token),
+            vec![range(0, 0, 100), range(0, 100, 200), range(0, 200, 300)]
+        );
+    }
+}
@@ -1,132 +1,289 @@
+use std::collections::HashMap;
+
 use crate::chat::events::{
-    ChallengeMissionResultEvent, ChatEvent, DonationEvent, Event, EventMeta, MissionEvent,
-    SimplifiedUserEvent, SubscribeEvent,
+    BattleMissionResultEvent, ChallengeMissionResultEvent, ChatEvent, DonationEvent, Event,
+    EventMeta, GiftEvent, MissionEvent, MissionTotalEvent, SimplifiedUserEvent, StickerEvent,
+    SubscribeEvent,
+};
+use crate::chat::parser::user::parse_user_status;
+use crate::chat::parser::util::normalize_user_id;
+use crate::chat::types::{
+    ChatType, DonationType, Emoticon, GiftType, MissionType, User, UserStatus,
 };
-use crate::chat::types::{ChatType, DonationType, MissionType, User, UserStatus};
-use chrono::{DateTime, NaiveDateTime, Utc};
+use crate::error::{Error, Result};
+use crate::models::try_parse_soop_timestamp;
+use chrono::{DateTime, TimeDelta, Utc};
 use quick_xml::Reader;
 use quick_xml::events::Event as XmlEvent;
 
+/// 해석하지 못하거나 지원하지 않는 요소를 처리하는 방식
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VODChatParseMode {
+    /// 첫 번째 오류에서 파싱을 멈추고 오류를 반환합니다.
+    Strict,
+    /// 해석하지 못한 요소는 건너뛰고 경고로 모읍니다.
+    #[default]
+    Lenient,
+}
+
+/// VOD 채팅 XML을 파싱한 결과
+#[derive(Debug, Default)]
+pub struct ParsedVODChat {
+    pub events: Vec<Event>,
+    /// 느슨한 모드에서 건너뛴 요소의 오류 (`Error::VodChat`)
+    pub warnings: Vec<Error>,
+}
+
+/// VOD 채팅 XML을 파싱합니다. 이벤트 시간은 `broad_start`(한국 시간)를 기준으로 계산합니다.
+/// 해석하지 못한 요소는 건너뜁니다. 오류로 받으려면 `VODChatParseMode::Strict`로
+/// `parse_vod_chat_xml`을 사용하세요.
+/// 여러 파일로 나뉜 VOD라면 파일마다 `parse_vod_file_chat_xml`을 사용하세요.
 pub fn parse_vod_chat_xml_with_start_time(
     xml_content: &str,
     broad_start: Option<&str>,
-) -> Result<Vec<Event>, Box<dyn std::error::Error>> {
+) -> Result<Vec<Event>> {
+    parse_vod_chat_xml(xml_content, broad_start, VODChatParseMode::Lenient)
+        .map(|parsed| parsed.events)
+}
+
+/// 처리 방식을 지정해 VOD 채팅 XML을 파싱합니다.
+/// 이벤트 시간은 `broad_start`(한국 시간)를 기준으로 계산합니다.
+pub fn parse_vod_chat_xml(
+    xml_content: &str,
+    broad_start: Option<&str>,
+    mode: VODChatParseMode,
+) -> Result<ParsedVODChat> {
+    let clock = VodClock {
+        file_start: broad_start.and_then(try_parse_soop_timestamp),
+        file_offset: 0.0,
+    };
+    parse_vod_chat_xml_with_clock(xml_content, clock, mode)
+}
+
+/// VOD 파일 하나의 채팅 XML을 파싱합니다.
+///
+/// XML의 시간은 파일 시작 기준이므로, `file_start`(파일 시작 시간)를 더해 이벤트 시간을 계산하고
+/// `file_offset`(VOD 시작부터 이 파일이 시작하기까지의 재생 시간, 초)을 더해 `vod_offset`을 채웁니다.
+pub fn parse_vod_file_chat_xml(
+    xml_content: &str,
+    file_start: DateTime<Utc>,
+    file_offset: f64,
+    mode: VODChatParseMode,
+) -> Result<ParsedVODChat> {
+    let clock = VodClock {
+        file_start: Some(file_start),
+        file_offset,
+    };
+    parse_vod_chat_xml_with_clock(xml_content, clock, mode)
+}
+
+/// 시간 기준을 지정해 VOD 채팅 XML을 파싱합니다.
+///
+/// 최상위 요소(`<root>`) 안의 요소는 모두 이벤트로 변환하며, 지원하지 않는 요소도 오류로 처리해
+/// 빠진 데이터가 없는지 확인할 수 있게 합니다.
+/// XML 문법 오류(`Error::Xml`)는 처리 방식과 관계없이 파싱을 멈춥니다.
+pub(crate) fn parse_vod_chat_xml_with_clock(
+    xml_content: &str,
+    clock: VodClock,
+    mode: VODChatParseMode,
+) -> Result<ParsedVODChat> {
     let mut reader = Reader::from_str(xml_content);
     reader.config_mut().trim_text(true);
 
-    let mut events = Vec::new();
+    let mut parsed = ParsedVODChat::default();
     let mut buf = Vec::new();
+    // 이벤트 요소를 담는 최상위 요소 안에 있는지 여부
+    let mut in_root = false;
 
     loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(XmlEvent::Start(ref e)) => match e.name().as_ref() {
-                b"chat" => {
-                    if let Ok(event) = parse_chat_element(&mut reader, broad_start) {
-                        events.push(event);
-                    }
-                }
-                b"follow" => {
-                    if let Ok(event) = parse_follow_element(&mut reader) {
-                        events.push(event);
-                    }
-                }
-                b"adballoon" => {
-                    if let Ok(event) = parse_adballoon_element(&mut reader) {
-                        events.push(event);
-                    }
-                }
-                b"fanclub" => {
-                    if let Ok(event) = parse_fanclub_element(&mut reader) {
-                        events.push(event);
-                    }
-                }
-                b"balloon" => {
-                    if let Ok(event) = parse_balloon_element(&mut reader) {
-                        events.push(event);
-                    }
-                }
-                b"challenge_mission" => {
-                    if let Ok(event) = parse_challenge_mission_element(&mut reader) {
-                        events.push(event);
-                    }
+        let element = match reader.read_event_into(&mut buf)? {
+            XmlEvent::Start(_) if !in_root => {
+                in_root = true;
+                None
+            }
+            // 이벤트 요소는 닫는 태그까지 함께 읽으므로, 여기서는 최상위 요소만 닫힙니다.
+            XmlEvent::End(_) => {
+                in_root = false;
+                None
+            }
+            XmlEvent::Start(ref e) => {
+                // 앞의 공백을 제외한 여는 태그('<')의 위치
+                let offset = reader.buffer_position() - (e.len() as u64 + 2);
+                let name = String::from_utf8_lossy(e.name().as_ref()).to_string();
+                Some(read_element(&mut reader, name, offset))
+            }
+            // 내용이 없는 요소(`<name/>`)
+            XmlEvent::Empty(ref e) if in_root => Some(Ok(VodElement {
+                name: String::from_utf8_lossy(e.name().as_ref()).to_string(),
+                offset: reader.buffer_position() - (e.len() as u64 + 3),
+                fields: HashMap::new(),
+            })),
+            XmlEvent::Eof => break,
+            _ => None,
+        };
+
+        if let Some(element) = element {
+            match element.and_then(|element| element.into_event(clock)) {
+                Ok(event) => parsed.events.push(event),
+                Err(e @ Error::VodChat { .. }) if mode == VODChatParseMode::Lenient => {
+                    parsed.warnings.push(e)
                 }
-                _ => {}
-            },
-            Ok(XmlEvent::Eof) => break,
-            Err(e) => return Err(Box::new(e)),
-            _ => {}
+                Err(e) => return Err(e),
+            }
         }
         buf.clear();
     }
 
-    Ok(events)
+    Ok(parsed)
 }
 
-fn calculate_event_time(broad_start: Option<&str>, timestamp_seconds: f64) -> DateTime<Utc> {
-    if let Some(start_time_str) = broad_start {
-        if let Ok(naive_start) = NaiveDateTime::parse_from_str(start_time_str, "%Y-%m-%d %H:%M:%S")
-        {
-            let start_utc = naive_start.and_utc();
-            let duration = chrono::Duration::milliseconds((timestamp_seconds * 1000.0) as i64);
-            return start_utc + duration;
+/// 이벤트 시간을 계산하는 기준
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct VodClock {
+    /// XML 시간의 기준이 되는 시작 시간, 알 수 없으면 현재 시간을 사용합니다.
+    pub file_start: Option<DateTime<Utc>>,
+    /// VOD 시작부터 `file_start`까지의 재생 시간(초)
+    pub file_offset: f64,
+}
+
+impl VodClock {
+    /// XML의 시간(초)으로 이벤트 메타 정보를 만듭니다.
+    /// 시간이 유한한 수가 아니거나 나타낼 수 있는 범위를 넘으면, `offset`의 요소에 대한 오류를 반환합니다.
+    fn meta(&self, timestamp_seconds: f64, offset: u64) -> Result<EventMeta> {
+        let out_of_range = || Error::VodChat {
+            offset,
+            reason: format!("시간이 범위를 벗어났습니다: {}", timestamp_seconds),
+        };
+
+        let millis = timestamp_seconds * 1000.0;
+        if !millis.is_finite() || millis.abs() >= i64::MAX as f64 {
+            return Err(out_of_range());
         }
+        let elapsed = TimeDelta::try_milliseconds(millis as i64).ok_or_else(out_of_range)?;
+        let received_time = match self.file_start {
+            Some(start) => start.checked_add_signed(elapsed).ok_or_else(out_of_range)?,
+            None => Utc::now(),
+        };
+
+        let mut meta = EventMeta::new(received_time);
+        meta.vod_offset = Some(self.file_offset + timestamp_seconds);
+        Ok(meta)
     }
-    Utc::now()
 }
 
-fn parse_chat_element(
-    reader: &mut Reader<&[u8]>,
-    broad_start: Option<&str>,
-) -> Result<Event, Box<dyn std::error::Error>> {
-    let mut message = String::new();
-    let mut user_id = String::new();
-    let mut nickname = String::new();
-    let mut timestamp = 0.0;
+/// 이벤트 요소 하나, 자식 요소의 이름과 내용(텍스트 또는 CDATA)을 담습니다.
+///
+/// 자식 요소는 라이브 채팅 패킷과 같은 정보를 담습니다.
+/// - `u`, `n`: 사용자 ID, 닉네임 (선물은 보낸 사람)
+/// - `t`: 파일 시작부터의 시간(초)
+/// - `f`: 사용자 플래그 (`combined|follow`)
+/// - `m`: 채팅 내용
+/// - `c`: 개수 (별풍선, 스티커, 미션 후원 등)
+struct VodElement {
+    name: String,
+    // XML에서 요소가 시작하는 위치 (바이트)
+    offset: u64,
+    fields: HashMap<String, String>,
+}
+
+fn read_element(reader: &mut Reader<&[u8]>, name: String, offset: u64) -> Result<VodElement> {
+    let mut fields = HashMap::new();
+    let mut field: Option<String> = None;
     let mut buf = Vec::new();
 
     loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(XmlEvent::Start(ref e)) => match e.name().as_ref() {
-                b"m" => {
-                    if let Ok(XmlEvent::CData(cdata)) = reader.read_event_into(&mut buf) {
-                        message = String::from_utf8_lossy(&cdata).to_string();
-                    }
+        match reader.read_event_into(&mut buf)? {
+            XmlEvent::Start(e) => {
+                field = Some(String::from_utf8_lossy(e.name().as_ref()).to_string());
+            }
+            XmlEvent::Text(text) => {
+                if let Some(field) = &field {
+                    fields.insert(field.clone(), String::from_utf8_lossy(&text).to_string());
                 }
-                b"u" => {
-                    if let Ok(XmlEvent::Text(text)) = reader.read_event_into(&mut buf) {
-                        user_id = String::from_utf8_lossy(&text).to_string();
-                    }
+            }
+            XmlEvent::CData(cdata) => {
+                if let Some(field) = &field {
+                    fields.insert(field.clone(), String::from_utf8_lossy(&cdata).to_string());
                 }
-                b"n" => {
-                    if let Ok(XmlEvent::CData(cdata)) = reader.read_event_into(&mut buf) {
-                        nickname = String::from_utf8_lossy(&cdata).to_string();
-                    }
-                }
-                b"t" => {
-                    if let Ok(XmlEvent::Text(text)) = reader.read_event_into(&mut buf) {
-                        timestamp = String::from_utf8_lossy(&text).parse().unwrap_or(0.0);
-                    }
-                }
-                _ => {}
-            },
-            Ok(XmlEvent::End(ref e)) if e.name().as_ref() == b"chat" => break,
-            Ok(XmlEvent::Eof) => break,
-            Err(e) => return Err(Box::new(e)),
+            }
+            XmlEvent::End(e) if e.name().as_ref() == name.as_bytes() => break,
+            XmlEvent::End(_) => field = None,
+            XmlEvent::Eof => {
+                return Err(Error::VodChat {
+                    offset,
+                    reason: format!("<{}> 요소가 닫히지 않았습니다.", name),
+                });
+            }
             _ => {}
         }
         buf.clear();
     }
 
-    Ok(Event::Chat(ChatEvent {
-        meta: EventMeta {
-            received_time: calculate_event_time(broad_start, timestamp),
-        },
-        comment: message,
-        chat_type: ChatType::Common,
-        user: User {
-            id: user_id,
-            label: nickname,
-            status: UserStatus {
+    Ok(VodElement {
+        name,
+        offset,
+        fields,
+    })
+}
+
+impl VodElement {
+    fn invalid(&self, reason: impl std::fmt::Display) -> Error {
+        Error::VodChat {
+            offset: self.offset,
+            reason: format!("<{}> {}", self.name, reason),
+        }
+    }
+
+    /// 선택 항목의 내용, 없으면 빈 문자열입니다.
+    fn text(&self, field: &str) -> &str {
+        self.fields
+            .get(field)
+            .map(String::as_str)
+            .unwrap_or_default()
+    }
+
+    fn required(&self, field: &str) -> Result<&str> {
+        match self.text(field) {
+            "" => Err(self.invalid(format!("{} 항목이 없습니다.", field))),
+            value => Ok(value),
+        }
+    }
+
+    /// 선택 숫자 항목, 없으면 `None`입니다.
+    fn optional_number(&self, field: &str) -> Result<Option<u32>> {
+        match self.text(field) {
+            "" => Ok(None),
+            value => value
+                .parse()
+                .map(Some)
+                .map_err(|_| self.invalid(format!("{} 항목이 숫자가 아닙니다: {}", field, value))),
+        }
+    }
+
+    /// 선택 숫자 항목, 없으면 0입니다.
+    fn number(&self, field: &str) -> Result<u32> {
+        Ok(self.optional_number(field)?.unwrap_or(0))
+    }
+
+    fn user_id(&self) -> Result<String> {
+        Ok(normalize_user_id(self.required("u")?))
+    }
+
+    fn nickname(&self) -> String {
+        self.text("n").to_string()
+    }
+
+    fn meta(&self, clock: VodClock) -> Result<EventMeta> {
+        let time = self.required("t")?;
+        let seconds = time
+            .parse()
+            .map_err(|_| self.invalid(format!("t 항목이 숫자가 아닙니다: {}", time)))?;
+        clock.meta(seconds, self.offset)
+    }
+
+    fn user(&self) -> Result<User> {
+        let status = match self.text("f") {
+            "" => UserStatus {
                 follow: 0,
                 is_bj: false,
                 is_manager: false,
@@ -134,300 +291,318 @@ fn parse_chat_element(
                 is_fan: false,
                 is_supporter: false,
             },
+            flags => parse_user_status(flags)
+                .ok_or_else(|| self.invalid(format!("f 항목을 해석할 수 없습니다: {}", flags)))?,
+        };
+
+        Ok(User {
+            id: self.user_id()?,
+            label: self.nickname(),
+            status,
             subscribe: None,
-        },
-        is_admin: false,
-        emoticon: None,
-    }))
-}
+        })
+    }
 
-fn parse_follow_element(reader: &mut Reader<&[u8]>) -> Result<Event, Box<dyn std::error::Error>> {
-    let mut user_id = String::new();
-    let mut nickname = String::new();
-    let mut buf = Vec::new();
+    fn chat(&self, clock: VodClock, chat_type: ChatType) -> Result<ChatEvent> {
+        Ok(ChatEvent {
+            meta: self.meta(clock)?,
+            comment: self.text("m").replace("\r", ""),
+            chat_type,
+            user: self.user()?,
+            is_admin: false,
+            emoticon: None,
+        })
+    }
 
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(XmlEvent::Start(ref e)) => match e.name().as_ref() {
-                b"u" => {
-                    if let Ok(XmlEvent::Text(text)) = reader.read_event_into(&mut buf) {
-                        user_id = String::from_utf8_lossy(&text).to_string();
-                    }
-                }
-                b"n" => {
-                    if let Ok(XmlEvent::CData(cdata)) = reader.read_event_into(&mut buf) {
-                        nickname = String::from_utf8_lossy(&cdata).to_string();
-                    }
-                }
-                _ => {}
-            },
-            Ok(XmlEvent::End(ref e)) if e.name().as_ref() == b"follow" => break,
-            Ok(XmlEvent::Eof) => break,
-            Err(e) => return Err(Box::new(e)),
-            _ => {}
-        }
-        buf.clear();
+    fn donation(
+        &self,
+        clock: VodClock,
+        amount: u32,
+        donation_type: DonationType,
+    ) -> Result<DonationEvent> {
+        Ok(DonationEvent {
+            meta: self.meta(clock)?,
+            from: self.user_id()?,
+            from_label: self.nickname(),
+            amount,
+            fan_club_ordinal: self.number("fo")?,
+            become_top_fan: self.text("tf") == "1",
+            donation_type,
+        })
     }
 
-    Ok(Event::Subscribe(SubscribeEvent {
-        meta: EventMeta {
-            received_time: Utc::now(),
-        },
-        user_id,
-        label: nickname,
-        tier: 1,
-        renew: 0,
-    }))
-}
+    fn mission(&self, clock: VodClock, mission_type: MissionType) -> Result<MissionEvent> {
+        Ok(MissionEvent {
+            meta: self.meta(clock)?,
+            from: self.user_id()?,
+            from_label: self.nickname(),
+            amount: self.number("c")?,
+            mission_type,
+        })
+    }
 
-fn parse_adballoon_element(
-    reader: &mut Reader<&[u8]>,
-) -> Result<Event, Box<dyn std::error::Error>> {
-    let mut user_id = String::new();
-    let mut nickname = String::new();
-    let mut buf = Vec::new();
+    fn mission_total(
+        &self,
+        clock: VodClock,
+        mission_type: MissionType,
+    ) -> Result<MissionTotalEvent> {
+        Ok(MissionTotalEvent {
+            meta: self.meta(clock)?,
+            mission_type,
+            amount: self.number("c")?,
+        })
+    }
 
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(XmlEvent::Start(ref e)) => match e.name().as_ref() {
-                b"u" => {
-                    if let Ok(XmlEvent::Text(text)) = reader.read_event_into(&mut buf) {
-                        user_id = String::from_utf8_lossy(&text).to_string();
-                    }
+    /// 라이브 파서와 같은 이벤트로 변환합니다.
+    fn into_event(self, clock: VodClock) -> Result<Event> {
+        let event = match self.name.as_str() {
+            "chat" => Event::Chat(self.chat(clock, ChatType::Common)?),
+            "emoticon" => Event::Chat(ChatEvent {
+                emoticon: Some(Emoticon {
+                    id: self.required("ei")?.to_string(),
+                    number: self.text("en").to_string(),
+                    ext: self.text("ee").to_string(),
+                    version: self.text("ev").to_string(),
+                }),
+                ..self.chat(clock, ChatType::Emoticon)?
+            }),
+            "manager_chat" => Event::Chat(ChatEvent {
+                is_admin: self.text("admin") == "1",
+                ..self.chat(clock, ChatType::Manager)?
+            }),
+            "follow" => Event::Subscribe(SubscribeEvent {
+                meta: self.meta(clock)?,
+                user_id: self.user_id()?,
+                label: self.nickname(),
+                tier: self.number("tier")?.max(1),
+                renew: self.number("renew")?,
+            }),
+            "gift" => Event::Gift(GiftEvent {
+                meta: self.meta(clock)?,
+                gift_type: GiftType::Subscription,
+                sender_id: self.user_id()?,
+                sender_label: self.nickname(),
+                receiver_id: normalize_user_id(self.required("ru")?),
+                receiver_label: self.text("rn").to_string(),
+                gift_code: self.text("gc").to_string(),
+            }),
+            // 애드벌룬 개수가 없는 기록은 1개로 봅니다.
+            "adballoon" => {
+                let amount = self.optional_number("c")?.unwrap_or(1);
+                Event::Donation(self.donation(clock, amount, DonationType::ADBalloon)?)
+            }
+            "balloon" => {
+                // 개수는 `c`에, 이전 기록은 `fn`의 마지막 '_' 뒤에 있습니다.
+                let amount = match self.optional_number("c")? {
+                    Some(amount) => amount,
+                    None => self
+                        .text("fn")
+                        .rsplit_once('_')
+                        .and_then(|(_, amount)| amount.parse().ok())
+                        .ok_or_else(|| self.invalid("별풍선 개수가 없습니다."))?,
+                };
+                Event::Donation(self.donation(clock, amount, DonationType::Balloon)?)
+            }
+            "video_balloon" => {
+                let amount = self.number("c")?;
+                Event::Donation(self.donation(clock, amount, DonationType::VODBalloon)?)
+            }
+            "sticker" => Event::Sticker(StickerEvent {
+                meta: self.meta(clock)?,
+                from: self.user_id()?,
+                from_label: self.nickname(),
+                amount: self.number("c")?,
+                supporter_ordinal: self.number("so")?,
+            }),
+            "fanclub" => Event::Join(SimplifiedUserEvent {
+                meta: self.meta(clock)?,
+                user_id: self.user_id()?,
+            }),
+            "challenge_mission" => match self.text("type") {
+                "CHALLENGE_SETTLE" => {
+                    Event::MissionTotal(self.mission_total(clock, MissionType::Challenge)?)
                 }
-                b"n" => {
-                    if let Ok(XmlEvent::CData(cdata)) = reader.read_event_into(&mut buf) {
-                        nickname = String::from_utf8_lossy(&cdata).to_string();
-                    }
+                "CHALLENGE_NOTICE" => Event::ChallengeMissionResult(ChallengeMissionResultEvent {
+                    meta: self.meta(clock)?,
+                    is_success: self.text("ms") == "SUCCESS",
+                    title: self.text("title").to_string(),
+                }),
+                // CHALLENGE_GIFT와 알 수 없는 타입은 미션 후원으로 처리
+                _ => Event::MissionDonation(self.mission(clock, MissionType::Challenge)?),
+            },
+            "battle_mission" => match self.text("type") {
+                "BATTLE_SETTLE" => {
+                    Event::MissionTotal(self.mission_total(clock, MissionType::Battle)?)
                 }
-                _ => {}
+                "BATTLE_NOTICE" => Event::BattleMissionResult(BattleMissionResultEvent {
+                    meta: self.meta(clock)?,
+                    is_draw: self.text("draw") == "1",
+                    winner: self.text("winner").to_string(),
+                    title: self.text("title").to_string(),
+                }),
+                // BATTLE_GIFT와 알 수 없는 타입은 미션 후원으로 처리
+                _ => Event::MissionDonation(self.mission(clock, MissionType::Battle)?),
             },
-            Ok(XmlEvent::End(ref e)) if e.name().as_ref() == b"adballoon" => break,
-            Ok(XmlEvent::Eof) => break,
-            Err(e) => return Err(Box::new(e)),
-            _ => {}
-        }
-        buf.clear();
+            _ => return Err(self.invalid("지원하지 않는 요소입니다.")),
+        };
+        Ok(event)
     }
-
-    Ok(Event::Donation(DonationEvent {
-        meta: EventMeta {
-            received_time: Utc::now(),
-        },
-        from: user_id,
-        from_label: nickname,
-        amount: 1,
-        fan_club_ordinal: 0,
-        become_top_fan: false,
-        donation_type: DonationType::ADBalloon,
-    }))
 }
 
-fn parse_fanclub_element(reader: &mut Reader<&[u8]>) -> Result<Event, Box<dyn std::error::Error>> {
-    let mut user_id = String::new();
-    let mut nickname = String::new();
-    let mut buf = Vec::new();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::parse_soop_timestamp;
 
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(XmlEvent::Start(ref e)) => match e.name().as_ref() {
-                b"u" => {
-                    if let Ok(XmlEvent::Text(text)) = reader.read_event_into(&mut buf) {
-                        user_id = String::from_utf8_lossy(&text).to_string();
-                    }
-                }
-                b"n" => {
-                    if let Ok(XmlEvent::CData(cdata)) = reader.read_event_into(&mut buf) {
-                        nickname = String::from_utf8_lossy(&cdata).to_string();
-                    }
-                }
-                _ => {}
-            },
-            Ok(XmlEvent::End(ref e)) if e.name().as_ref() == b"fanclub" => break,
-            Ok(XmlEvent::Eof) => break,
-            Err(e) => return Err(Box::new(e)),
-            _ => {}
-        }
-        buf.clear();
+    // 픽스처는 실제 응답을 캡처한 것이 아니라 직접 작성한 예시입니다.
+    // 아래 테스트는 파서가 가정한 형식(파일별 시작 시간, 한국 시간 기준)대로 동작하는지만 확인합니다.
+    const FILE1: &str = include_str!("../tests/fixtures/vod_chat/file1.xml");
+    const FILE2: &str = include_str!("../tests/fixtures/vod_chat/file2.xml");
+    const ELEMENTS: &str = include_str!("../tests/fixtures/vod_chat/elements.xml");
+
+    fn time(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value).unwrap().to_utc()
     }
 
-    Ok(Event::Join(SimplifiedUserEvent {
-        meta: EventMeta {
-            received_time: Utc::now(),
-        },
-        user_id,
-    }))
-}
+    #[test]
+    fn test_event_times_follow_file_start() {
+        // 방송 시작 시간은 한국 시간이므로 라이브 API와 같이 UTC로 변환됩니다.
+        let events =
+            parse_vod_chat_xml_with_start_time(FILE1, Some("2024-01-01 13:00:00")).unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(
+            events[0].meta().received_time,
+            time("2024-01-01T04:00:12.500Z")
+        );
+        assert_eq!(events[0].meta().vod_offset, Some(12.5));
+        assert_eq!(events[2].meta().vod_offset, Some(301.25));
 
-fn parse_balloon_element(reader: &mut Reader<&[u8]>) -> Result<Event, Box<dyn std::error::Error>> {
-    let mut user_id = String::new();
-    let mut nickname = String::new();
-    let mut amount = 0u32;
-    let mut buf = Vec::new();
+        // 두 번째 파일은 파일 시작 시간과, 앞 파일 길이만큼의 재생 시간을 기준으로 합니다.
+        let file_start = parse_soop_timestamp("2024-01-01 14:00:10");
+        let events = parse_vod_file_chat_xml(FILE2, file_start, 3600.0, VODChatParseMode::Strict)
+            .unwrap()
+            .events;
+        assert!(matches!(&events[0], Event::Chat(e) if e.comment == "2부 시작"));
+        assert_eq!(events[0].meta().received_time, time("2024-01-01T05:00:15Z"));
+        assert_eq!(events[0].meta().vod_offset, Some(3605.0));
+        assert_eq!(events[1].meta().vod_offset, Some(3607.5));
+    }
 
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(XmlEvent::Start(ref e)) => match e.name().as_ref() {
-                b"u" => {
-                    if let Ok(XmlEvent::Text(text)) = reader.read_event_into(&mut buf) {
-                        user_id = String::from_utf8_lossy(&text).to_string();
-                    }
-                }
-                b"n" => {
-                    if let Ok(XmlEvent::CData(cdata)) = reader.read_event_into(&mut buf) {
-                        nickname = String::from_utf8_lossy(&cdata).to_string();
-                    }
-                }
-                b"fn" => {
-                    if let Ok(XmlEvent::Text(text)) = reader.read_event_into(&mut buf) {
-                        let amount_str = String::from_utf8_lossy(&text);
-
-                        if let Some(underscore_pos) = amount_str.rfind('_') {
-                            if let Ok(parsed_amount) =
-                                amount_str[underscore_pos + 1..].parse::<u32>()
-                            {
-                                amount = parsed_amount;
-                            }
-                        }
-                    }
-                }
-                b"c" => {
-                    if let Ok(XmlEvent::Text(text)) = reader.read_event_into(&mut buf) {
-                        if let Ok(parsed_amount) = String::from_utf8_lossy(&text).parse::<u32>() {
-                            amount = parsed_amount;
-                        }
-                    }
-                }
-                _ => {}
-            },
-            Ok(XmlEvent::End(ref e)) if e.name().as_ref() == b"balloon" => break,
-            Ok(XmlEvent::Eof) => break,
-            Err(e) => return Err(Box::new(e)),
-            _ => {}
+    #[test]
+    fn test_elements_map_to_live_events() {
+        let events =
+            parse_vod_chat_xml_with_start_time(ELEMENTS, Some("2024-01-01 09:00:00")).unwrap();
+        assert_eq!(events.len(), 13);
+
+        // 모든 요소가 <t>의 시간을 사용합니다.
+        for (i, event) in events.iter().enumerate() {
+            assert_eq!(event.meta().vod_offset, Some(i as f64 + 1.0));
+            assert_eq!(
+                event.meta().received_time,
+                time("2024-01-01T00:00:00Z") + chrono::Duration::seconds(i as i64 + 1)
+            );
         }
-        buf.clear();
+
+        assert!(matches!(&events[0], Event::Chat(e)
+            if e.user.id == "viewer1" && e.user.status.is_fan && e.emoticon.is_none()));
+        assert!(matches!(&events[1], Event::Chat(e)
+            if matches!(e.chat_type, ChatType::Emoticon)
+                && e.emoticon.as_ref().is_some_and(|emoticon| emoticon.id == "12345")));
+        assert!(matches!(&events[2], Event::Chat(e)
+            if matches!(e.chat_type, ChatType::Manager) && e.is_admin));
+        assert!(matches!(&events[3], Event::Subscribe(e) if e.tier == 2 && e.renew == 3));
+        assert!(matches!(&events[4], Event::Gift(e)
+            if e.sender_id == "viewer3" && e.receiver_id == "viewer4" && e.gift_code == "GIFT01"));
+        assert!(matches!(&events[5], Event::Donation(e)
+            if matches!(e.donation_type, DonationType::ADBalloon) && e.amount == 30));
+        assert!(matches!(&events[6], Event::Donation(e)
+            if matches!(e.donation_type, DonationType::Balloon) && e.amount == 10));
+        assert!(matches!(&events[7], Event::Donation(e)
+            if matches!(e.donation_type, DonationType::VODBalloon)
+                && e.amount == 50
+                && e.fan_club_ordinal == 3));
+        assert!(matches!(&events[8], Event::Sticker(e)
+            if e.amount == 20 && e.supporter_ordinal == 4));
+        assert!(matches!(&events[9], Event::Join(e) if e.user_id == "viewer9"));
+        assert!(matches!(&events[10], Event::MissionTotal(e)
+            if matches!(e.mission_type, MissionType::Challenge) && e.amount == 1000));
+        assert!(matches!(&events[11], Event::MissionDonation(e)
+            if matches!(e.mission_type, MissionType::Battle) && e.amount == 100));
+        assert!(matches!(&events[12], Event::BattleMissionResult(e)
+            if !e.is_draw && e.winner == "A팀" && e.title == "대결"));
     }
 
-    Ok(Event::Donation(DonationEvent {
-        meta: EventMeta {
-            received_time: Utc::now(),
-        },
-        from: user_id,
-        from_label: nickname,
-        amount,
-        fan_club_ordinal: 0,
-        become_top_fan: false,
-        donation_type: DonationType::Balloon,
-    }))
-}
+    #[test]
+    fn test_out_of_range_time_is_rejected() {
+        let start = Some("2024-01-01 09:00:00");
+        for time in ["inf", "-inf", "NaN", "1e300"] {
+            let xml = format!("<root><chat><u>viewer1</u><m>채팅</m><t>{time}</t></chat></root>");
+            let result = parse_vod_chat_xml(&xml, start, VODChatParseMode::Strict);
+            assert!(
+                matches!(result, Err(Error::VodChat { offset: 6, .. })),
+                "{time}: {result:?}"
+            );
+        }
+    }
 
-fn parse_challenge_mission_element(
-    reader: &mut Reader<&[u8]>,
-) -> Result<Event, Box<dyn std::error::Error>> {
-    let mut mission_type_str = String::new();
-    let mut user_id = String::new();
-    let mut nickname = String::new();
-    let mut amount = 0u32;
-    let mut title = String::new();
-    let mut success_status = String::new();
-    let mut buf = Vec::new();
+    #[test]
+    fn test_invalid_element_by_parse_mode() {
+        let xml = r#"<root>
+<chat><u>viewer1</u><m>첫 채팅</m><t>1</t></chat>
+<chat><u>viewer2</u><m>시간 오류</m><t>abc</t></chat>
+<chat><u>viewer3</u><m>세 번째 채팅</m><t>3</t></chat>
+</root>"#;
+        let start = Some("2024-01-01 09:00:00");
 
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(XmlEvent::Start(ref e)) => match e.name().as_ref() {
-                b"type" => {
-                    if let Ok(XmlEvent::Text(text)) = reader.read_event_into(&mut buf) {
-                        mission_type_str = String::from_utf8_lossy(&text).to_string();
-                    }
-                }
-                b"u" => {
-                    if let Ok(XmlEvent::Text(text)) = reader.read_event_into(&mut buf) {
-                        user_id = String::from_utf8_lossy(&text).to_string();
-                    }
-                }
-                b"n" => {
-                    if let Ok(XmlEvent::CData(cdata)) = reader.read_event_into(&mut buf) {
-                        nickname = String::from_utf8_lossy(&cdata).to_string();
-                    }
-                }
-                b"title" => {
-                    if let Ok(XmlEvent::CData(cdata)) = reader.read_event_into(&mut buf) {
-                        title = String::from_utf8_lossy(&cdata).to_string();
-                    }
-                }
-                b"c" => {
-                    if let Ok(XmlEvent::Text(text)) = reader.read_event_into(&mut buf) {
-                        if let Ok(parsed_amount) = String::from_utf8_lossy(&text).parse::<u32>() {
-                            amount = parsed_amount;
-                        }
-                    }
-                }
-                b"ms" => {
-                    if let Ok(XmlEvent::Text(text)) = reader.read_event_into(&mut buf) {
-                        success_status = String::from_utf8_lossy(&text).to_string();
-                    }
-                }
-                _ => {}
-            },
-            Ok(XmlEvent::End(ref e)) if e.name().as_ref() == b"challenge_mission" => break,
-            Ok(XmlEvent::Eof) => break,
-            Err(e) => return Err(Box::new(e)),
-            _ => {}
-        }
-        buf.clear();
+        // 느슨한 모드는 잘못된 요소만 건너뛰고 그 위치를 경고로 남깁니다.
+        let parsed = parse_vod_chat_xml(xml, start, VODChatParseMode::Lenient).unwrap();
+        assert_eq!(parsed.events.len(), 2);
+        assert_eq!(parsed.events[1].meta().vod_offset, Some(3.0));
+        assert!(
+            matches!(&parsed.warnings[..], [Error::VodChat { offset, reason }]
+            if xml[*offset as usize..].starts_with("<chat><u>viewer2") && reason.contains("abc"))
+        );
+
+        assert!(matches!(
+            parse_vod_chat_xml(xml, start, VODChatParseMode::Strict),
+            Err(Error::VodChat { .. })
+        ));
+
+        // 시작 시간만 받는 함수는 예전처럼 느슨하게 동작합니다.
+        let events = parse_vod_chat_xml_with_start_time(xml, start).unwrap();
+        assert_eq!(events.len(), 2);
+
+        // XML 자체가 깨진 경우는 모드와 관계없이 실패합니다.
+        assert!(matches!(
+            parse_vod_chat_xml("<root><chat></root>", start, VODChatParseMode::Lenient),
+            Err(Error::Xml(_))
+        ));
     }
 
-    match mission_type_str.as_str() {
-        "CHALLENGE_GIFT" => Ok(Event::MissionDonation(MissionEvent {
-            meta: EventMeta {
-                received_time: Utc::now(),
-            },
-            from: user_id,
-            from_label: nickname,
-            amount,
-            mission_type: MissionType::Challenge,
-        })),
-        "CHALLENGE_SETTLE" => {
-            // ! 정산은 MissionTotal 이벤트로 처리할 수도 있지만, 현재는 MissionDonation으로 처리
-            Ok(Event::MissionDonation(MissionEvent {
-                meta: EventMeta {
-                    received_time: Utc::now(),
-                },
-                from: if user_id.is_empty() {
-                    "system".to_string()
-                } else {
-                    user_id
-                },
-                from_label: if nickname.is_empty() {
-                    "시스템".to_string()
-                } else {
-                    nickname
-                },
-                amount,
-                mission_type: MissionType::Challenge,
-            }))
-        }
-        "CHALLENGE_NOTICE" => {
-            let is_success = success_status == "SUCCESS";
-            Ok(Event::ChallengeMissionResult(ChallengeMissionResultEvent {
-                meta: EventMeta {
-                    received_time: Utc::now(),
-                },
-                is_success,
-                title,
-            }))
-        }
-        _ => {
-            // 알 수 없는 타입은 일반 미션으로 처리
-            Ok(Event::MissionDonation(MissionEvent {
-                meta: EventMeta {
-                    received_time: Utc::now(),
-                },
-                from: user_id,
-                from_label: nickname,
-                amount,
-                mission_type: MissionType::Challenge,
-            }))
-        }
+    #[test]
+    fn test_unknown_element_by_parse_mode() {
+        let start = Some("2024-01-01 09:00:00");
+        let xml = r#"<root>
+<chat><u>viewer1</u><m>채팅</m><t>1</t></chat>
+<unknown><u>viewer2</u><t>2</t></unknown>
+<empty/>
+</root>"#;
+
+        // 지원하지 않는 요소도 건너뛰지 않고, 느슨한 모드는 경고로 남기고 엄격한 모드는 실패합니다.
+        let parsed = parse_vod_chat_xml(xml, start, VODChatParseMode::Lenient).unwrap();
+        assert_eq!(parsed.events.len(), 1);
+        let offsets: Vec<&str> = parsed
+            .warnings
+            .iter()
+            .map(|warning| match warning {
+                Error::VodChat { offset, .. } => &xml[*offset as usize..][..7],
+                _ => panic!("{warning:?}"),
+            })
+            .collect();
+        assert_eq!(offsets, vec!["<unknow", "<empty/"]);
+        assert!(matches!(
+            parse_vod_chat_xml(xml, start, VODChatParseMode::Strict),
+            Err(Error::VodChat { reason, .. }) if reason.starts_with("<unknown>")
+        ));
     }
 }